@@ -4,10 +4,20 @@ _arguments \
   '*'{-c+,--config=}'[Path(s) to configuration file(s)]:file:_files' \
   '(-t --tags)'{-t+,--tags=}'[Only process rules tagged with these values]:tag:' \
   '--skip-tags=[Ignore rules tagged with these values]:tag:' \
-  '(-f --format)'{-f+,--format=}'[Output format]:format:(by-root full-paths by-rule by-path json)' \
+  '(-f --format)'{-f+,--format=}'[Output format]:format:(by-root full-paths by-rule by-path json jsonl sarif checkstyle junit github gitlab csv tsv markdown html matrix)' \
   '--color=[Coloring]:mode:(auto always never)' \
   '--palette=[Palette to use for rule coloring]:palette:(none simple severity true-color)' \
   '--error-exitcode=[If any matches are found, exit with given code]:exit code:' \
+  '--keep-going[Report config errors, but continue with successfully parsed rules]' \
+  '--explain=[Instead of reporting matches, explain how rule with given id or title was evaluated]:rule id or title:' \
+  '(--write-baseline)--fix[Fix matched lines using replacements specified in rules]' \
+  '--dry-run[With --fix, print unified diff of changes instead of modifying files]' \
+  '(--baseline --fix)--write-baseline=[Record all current matches into given baseline file instead of reporting them]:file:_files' \
+  '(--write-baseline)--baseline=[Do not report matches recorded in given baseline file]:file:_files' \
+  '(-A --after-context)'{-A+,--after-context=}'[Print NUM lines of context after matched lines (text formats only)]:number:' \
+  '(-B --before-context)'{-B+,--before-context=}'[Print NUM lines of context before matched lines (text formats only)]:number:' \
+  '(-C --context)'{-C+,--context=}'[Print NUM lines of context around matched lines (text formats only)]:number:' \
+  '--summary[Print summary with match counts and amounts of processed data after the report]' \
   {-j+,--jobs=}'[Number of target directories to process simultaneously]:number:' \
   '(* -)'{-h,--help}'[Print help]' \
   '(* -)'{-V,--version}'[Print version]' \
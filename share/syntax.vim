@@ -7,17 +7,19 @@ if exists("b:current_syntax")
 endif
 
 syn keyword omnilinterTodo      contained TODO FIXME XXX
-syn match   omnilinterIgnore    contained "omnilinter: ignore"
+syn match   omnilinterIgnore    contained "omnilinter: \(ignore\|disable\|enable\)"
 syn match   omnilinterComment   "#.*" contains=omnilinterIgnore,omnilinterTodo,@Spell
 syn match   omnilinterTag       "[^ \t,]\+" contained
 
 syn region  omnilinterRuleTitle     skipwhite keepend start=+\[+ skip=+\]\]+ end=+\]+ 
-syn keyword omnilinterDirective     tags files nofiles match nomatch size lines
+syn keyword omnilinterDirective     id tags files nofiles match matches replace nomatch size lines
+syn keyword omnilinterQualifier     within first last line then
 syn region  omnilinterDirectiveTags matchgroup=omnilinterDirective start=+^\s*tags+ skip=+,+ end=+\s*$+ contains=omnilinterTag
 
 hi def link omnilinterComment       Comment
 hi def link omnilinterRuleTitle     Keyword
 hi def link omnilinterDirective     Identifier
+hi def link omnilinterQualifier     Statement
 hi def link omnilinterIgnore        SpecialComment
 hi def link omnilinterTodo          Todo
 hi def link omnilinterTag           String
@@ -1,15 +1,26 @@
 // SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+mod context_lines;
+mod ignore_file;
+mod ignore_markers;
 mod matching_caches;
 
-use crate::r#match::{Match, MatchResult};
+use crate::explanation::{ContentConditionOutcome, Explanation, FileTrace};
+use crate::r#match::{LineContext, LineSpan, Match, MatchResult};
 use crate::ruleset::compile::CompiledRuleset;
-use crate::ruleset::{ConditionLogic, ContentCondition, GlobCondition, Rule};
+use crate::ruleset::{ConditionLogic, ContentCondition, GlobCondition, RegexCondition, Rule};
+use context_lines::ContextCollector;
+pub use context_lines::ContextLines;
+use ignore_file::IgnoreFile;
+pub use ignore_file::{UnusedIgnoreEntry, warn_about_unused_ignore_entries};
+use ignore_markers::{
+    IGNORE_FILE_MARKER_MAX_BYTES, IGNORE_FILE_MARKER_MAX_LINES, IgnoreMarkers, IgnoreScope,
+};
 use matching_caches::{GlobMatchingCache, RegexMatchingCache};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read, Seek};
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use walkdir::WalkDir;
@@ -18,15 +29,19 @@ fn apply_file_metadata_conditions(
     root: &Path,
     path: &Rc<PathBuf>,
     rules_with_conditions: &mut Vec<(&Rule, &GlobCondition)>,
+    outcomes: &mut [ContentConditionOutcome],
 ) -> Result<(), std::io::Error> {
     let size = std::fs::metadata(root.join(path.as_path()))?.len();
 
-    rules_with_conditions.retain(|(_, path_condition)| {
+    rules_with_conditions.retain(|(rule, path_condition)| {
         for content_condition_node in &path_condition.content_conditions {
-            if let ContentCondition::Size(size_condition) = &content_condition_node.condition
-                && !size_condition.check(size)
-            {
-                return false;
+            if let ContentCondition::Size(size_condition) = &content_condition_node.condition {
+                if rule.is_explained {
+                    outcomes[content_condition_node.number] = ContentConditionOutcome::Value(size);
+                }
+                if !size_condition.check(size) {
+                    return false;
+                }
             }
         }
         true
@@ -39,8 +54,12 @@ fn apply_file_line_conditions(
     num_lines: u64,
     rules_with_conditions: &mut Vec<(&Rule, &GlobCondition)>,
     rules_with_conditions_to_finalize: &mut HashMap<usize, (&Rule, &GlobCondition)>,
+    outcomes: &mut [ContentConditionOutcome],
 ) {
-    rules_with_conditions.retain(|(_, path_condition)| {
+    rules_with_conditions.retain(|(rule, path_condition)| {
+        if rule.is_explained {
+            record_complete_file_outcomes(path_condition, num_lines, outcomes);
+        }
         for content_condition_node in &path_condition.content_conditions {
             if let ContentCondition::Lines(size_condition) = &content_condition_node.condition
                 && !size_condition.check(num_lines)
@@ -53,6 +72,175 @@ fn apply_file_line_conditions(
     });
 }
 
+/// Check conditions limited to last lines of a file, which can
+/// only be resolved after the file was read completely
+fn apply_deferred_line_conditions(
+    num_lines: u64,
+    rules_with_conditions: &mut Vec<(&Rule, &GlobCondition)>,
+    rules_with_conditions_to_finalize: &mut HashMap<usize, (&Rule, &GlobCondition)>,
+    deferred_matched_lines: &mut [VecDeque<(u64, LineSpan)>],
+    local_condition_statuses: &mut [bool],
+    matched_lines: &mut [Vec<(u64, LineSpan)>],
+    outcomes: &mut [ContentConditionOutcome],
+) {
+    rules_with_conditions.retain(|(rule, path_condition)| {
+        for content_condition_node in &path_condition.content_conditions {
+            let regex_condition = match &content_condition_node.condition {
+                ContentCondition::Match(regex_condition)
+                | ContentCondition::NoMatch(regex_condition)
+                    if regex_condition.is_deferred() =>
+                {
+                    regex_condition
+                }
+                _ => continue,
+            };
+
+            let lines = &mut deferred_matched_lines[content_condition_node.number];
+            if let Some(line_range) = &regex_condition.line_range {
+                lines.retain(|(line_number, _)| line_range.contains(*line_number, num_lines));
+            }
+
+            if rule.is_explained {
+                lines.iter().for_each(|(line_number, _)| {
+                    record_matched_line(&mut outcomes[content_condition_node.number], *line_number)
+                });
+            }
+
+            if let ContentCondition::NoMatch(_) = content_condition_node.condition {
+                if !lines.is_empty() {
+                    rules_with_conditions_to_finalize.remove(&path_condition.number);
+                    return false;
+                }
+                continue;
+            }
+
+            let is_matched = match &regex_condition.count {
+                Some(count_condition) => count_condition.check(lines.len() as u64),
+                None => !lines.is_empty(),
+            };
+            if is_matched {
+                local_condition_statuses[content_condition_node.number] = true;
+                if content_condition_node.is_reporting_target {
+                    matched_lines[rule.number].extend(lines.iter().cloned());
+                }
+            }
+        }
+        true
+    });
+}
+
+/// Check whether the line matches regex condition
+///
+/// For conditions with follow-up patterns, the match is only reported
+/// for the line which completes the sequence, and the line of the last
+/// unfinished sequence start is tracked in `sequence_start`
+fn check_sequenced_condition_match(
+    matching_cache: &mut RegexMatchingCache,
+    regex_condition: &RegexCondition,
+    rule: &Rule,
+    sequence_start: &mut Option<u64>,
+    line_number: u64,
+) -> bool {
+    let Some(follow_up) = &regex_condition.follow_up else {
+        return matching_cache.check_condition_match(regex_condition, rule);
+    };
+
+    let mut is_completed = false;
+    if let Some(start_line_number) = *sequence_start {
+        if follow_up
+            .max_distance
+            .is_some_and(|max_distance| line_number - start_line_number > max_distance)
+        {
+            *sequence_start = None;
+        } else if matching_cache.check_condition_match(&follow_up.condition, rule) {
+            *sequence_start = None;
+            is_completed = true;
+        }
+    }
+
+    if matching_cache.check_condition_match(regex_condition, rule) {
+        *sequence_start = Some(line_number);
+    }
+
+    is_completed
+}
+
+/// Check numbers of matches counted for `matches` conditions
+fn apply_match_count_conditions(
+    rules_with_conditions_to_finalize: &HashMap<usize, (&Rule, &GlobCondition)>,
+    match_counts: &[u64],
+    local_condition_statuses: &mut [bool],
+) {
+    for (_, path_condition) in rules_with_conditions_to_finalize.values() {
+        for content_condition_node in &path_condition.content_conditions {
+            if let ContentCondition::Match(regex_condition) = &content_condition_node.condition
+                && let Some(count_condition) = &regex_condition.count
+                && !regex_condition.is_deferred()
+            {
+                local_condition_statuses[content_condition_node.number] =
+                    count_condition.check(match_counts[content_condition_node.number]);
+            }
+        }
+    }
+}
+
+fn get_line_span(line: &str, regex_condition: &RegexCondition) -> LineSpan {
+    LineSpan::new(line, regex_condition.find_span(line).unwrap_or_default())
+}
+
+/// Remember matched line of a deferred condition, forgetting lines
+/// which can no longer fall into condition's line range
+fn push_deferred_matched_line(
+    lines: &mut VecDeque<(u64, LineSpan)>,
+    regex_condition: &RegexCondition,
+    line_number: u64,
+    line: &str,
+) {
+    lines.push_back((line_number, get_line_span(line, regex_condition)));
+    if let Some(line_range) = &regex_condition.line_range {
+        while let Some((first_line_number, _)) = lines.front()
+            && !line_range.contains(*first_line_number, line_number + 1)
+        {
+            lines.pop_front();
+        }
+    }
+}
+
+/// Fill in outcomes of linewise conditions for a file which was read completely
+fn record_complete_file_outcomes(
+    path_condition: &GlobCondition,
+    num_lines: u64,
+    outcomes: &mut [ContentConditionOutcome],
+) {
+    for content_condition_node in &path_condition.content_conditions {
+        let outcome = &mut outcomes[content_condition_node.number];
+        match &content_condition_node.condition {
+            ContentCondition::Match(_) | ContentCondition::NoMatch(_)
+                if *outcome == ContentConditionOutcome::NotChecked =>
+            {
+                *outcome = ContentConditionOutcome::NotMatched;
+            }
+            ContentCondition::Lines(_) => {
+                *outcome = ContentConditionOutcome::Value(num_lines);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn record_matched_line(outcome: &mut ContentConditionOutcome, line_number: u64) {
+    if let ContentConditionOutcome::Matched(lines) = outcome {
+        lines.push(line_number);
+    } else {
+        *outcome = ContentConditionOutcome::Matched(vec![line_number]);
+    }
+}
+
+/// Check content conditions for a single file
+///
+/// Returns number of bytes read, or `None` if the file did not need to be read.
+/// For files which were read, `ignore-file` marker scope is recorded as well.
+#[allow(clippy::too_many_arguments)] // state shared between files is owned by the caller
 fn apply_content_rules(
     ruleset: &CompiledRuleset,
     root: &Path,
@@ -60,18 +248,26 @@ fn apply_content_rules(
     mut rules_with_conditions: Vec<(&Rule, &GlobCondition)>,
     global_rule_statuses: &mut [RuleMatchStatus],
     global_condition_statuses: &mut [bool],
-) -> Result<(), std::io::Error> {
-    apply_file_metadata_conditions(root, &path, &mut rules_with_conditions)?;
+    outcomes: &mut [ContentConditionOutcome],
+    context_lines: ContextLines,
+    file_ignore_scopes: &mut HashMap<Rc<PathBuf>, IgnoreScope>,
+) -> Result<Option<u64>, std::io::Error> {
+    apply_file_metadata_conditions(root, &path, &mut rules_with_conditions, outcomes)?;
 
     if rules_with_conditions.is_empty() {
-        return Ok(());
+        return Ok(None);
     }
 
     let file = File::open(root.join(path.as_path()))?;
-    let reader = BufReader::new(file);
+    let mut reader = BufReader::new(file);
 
     let mut local_condition_statuses: Vec<bool> = vec![false; global_condition_statuses.len()];
-    let mut matched_lines: Vec<Vec<u64>> = vec![Default::default(); global_rule_statuses.len()];
+    let mut matched_lines: Vec<Vec<(u64, LineSpan)>> =
+        vec![Default::default(); global_rule_statuses.len()];
+    let mut deferred_matched_lines: Vec<VecDeque<(u64, LineSpan)>> =
+        vec![Default::default(); global_condition_statuses.len()];
+    let mut match_counts: Vec<u64> = vec![0; global_condition_statuses.len()];
+    let mut sequence_starts: Vec<Option<u64>> = vec![None; global_condition_statuses.len()];
 
     let mut rules_with_conditions_to_finalize: HashMap<usize, (&Rule, &GlobCondition)> =
         rules_with_conditions
@@ -79,64 +275,221 @@ fn apply_content_rules(
             .map(|(rule, condition)| (condition.number, (*rule, *condition)))
             .collect();
 
+    let mut context_collector = ContextCollector::new(context_lines);
+
+    let mut next_line_ignore_scope: IgnoreScope = Default::default();
+    let mut disabled_scope: IgnoreScope = Default::default();
+    let mut file_ignore_scope: IgnoreScope = Default::default();
+
     let mut line_number: u64 = 0;
-    for line in reader.lines() {
+    for line in (&mut reader).lines() {
         let line = line?;
 
-        let mut matching_cache = RegexMatchingCache::new(&line, ruleset.regexes_count);
+        context_collector.add_following_line(&line);
+
+        let mut ignore_scope = std::mem::take(&mut next_line_ignore_scope);
+        if IgnoreMarkers::may_be_present(&line) {
+            // both disable and enable marker lines belong to the disabled block
+            let ignore_markers = IgnoreMarkers::parse(&line);
+            disabled_scope.merge(ignore_markers.disable);
+            next_line_ignore_scope = ignore_markers.next_line;
+            ignore_scope.merge(ignore_markers.this_line);
+            ignore_scope.merge(disabled_scope.clone());
+            disabled_scope.subtract(&ignore_markers.enable);
+            if line_number < IGNORE_FILE_MARKER_MAX_LINES as u64 {
+                file_ignore_scope.merge(ignore_markers.file);
+            }
+        } else {
+            ignore_scope.merge(disabled_scope.clone());
+        }
+
+        let mut matching_cache =
+            RegexMatchingCache::new(&line, &ignore_scope, ruleset.regexes_count);
 
         rules_with_conditions.retain(|(rule, path_condition)| {
-            let mut num_satisfied_content_conditions = 0;
+            let mut num_settled_content_conditions = 0;
             for content_condition_node in &path_condition.content_conditions {
-                match &content_condition_node.condition {
+                let is_settled = match &content_condition_node.condition {
+                    ContentCondition::Match(regex_condition)
+                    | ContentCondition::NoMatch(regex_condition)
+                        if regex_condition.is_deferred() =>
+                    {
+                        if check_sequenced_condition_match(
+                            &mut matching_cache,
+                            regex_condition,
+                            rule,
+                            &mut sequence_starts[content_condition_node.number],
+                            line_number,
+                        ) {
+                            if content_condition_node.is_reporting_target {
+                                context_collector.add_matched_line(line_number);
+                            }
+                            push_deferred_matched_line(
+                                &mut deferred_matched_lines[content_condition_node.number],
+                                regex_condition,
+                                line_number,
+                                &line,
+                            );
+                        }
+                        false
+                    }
+                    ContentCondition::NoMatch(regex_condition)
+                        if regex_condition.applies_to_line(line_number)
+                            && matching_cache.check_condition_match(regex_condition, rule) =>
+                    {
+                        if rule.is_explained {
+                            record_matched_line(
+                                &mut outcomes[content_condition_node.number],
+                                line_number,
+                            );
+                        }
+                        rules_with_conditions_to_finalize.remove(&path_condition.number);
+                        return false;
+                    }
                     ContentCondition::NoMatch(regex_condition) => {
-                        if matching_cache.check_condition_match(regex_condition) {
-                            rules_with_conditions_to_finalize.remove(&path_condition.number);
-                            return false;
+                        regex_condition.is_exhausted(line_number + 1)
+                    }
+                    ContentCondition::Match(
+                        regex_condition @ RegexCondition {
+                            count: Some(count_condition),
+                            ..
+                        },
+                    ) => {
+                        let num_matches = &mut match_counts[content_condition_node.number];
+                        if regex_condition.applies_to_line(line_number)
+                            && check_sequenced_condition_match(
+                                &mut matching_cache,
+                                regex_condition,
+                                rule,
+                                &mut sequence_starts[content_condition_node.number],
+                                line_number,
+                            )
+                        {
+                            *num_matches += 1;
+                            if content_condition_node.is_reporting_target {
+                                context_collector.add_matched_line(line_number);
+                                matched_lines[rule.number]
+                                    .push((line_number, get_line_span(&line, regex_condition)));
+                            }
+                            if rule.is_explained {
+                                record_matched_line(
+                                    &mut outcomes[content_condition_node.number],
+                                    line_number,
+                                );
+                            }
                         }
+                        (count_condition.check_for_this_and_above(*num_matches)
+                            && !content_condition_node.is_reporting_target)
+                            || regex_condition.is_exhausted(line_number + 1)
                     }
                     ContentCondition::Match(regex_condition) => {
                         let is_matched =
                             &mut local_condition_statuses[content_condition_node.number];
-                        if content_condition_node.is_reporting_target {
-                            if matching_cache.check_condition_match(regex_condition) {
+                        if !regex_condition.applies_to_line(line_number) {
+                            // line is outside of condition's line range
+                        } else if content_condition_node.is_reporting_target {
+                            if check_sequenced_condition_match(
+                                &mut matching_cache,
+                                regex_condition,
+                                rule,
+                                &mut sequence_starts[content_condition_node.number],
+                                line_number,
+                            ) {
                                 *is_matched = true;
-                                matched_lines[rule.number].push(line_number);
-                            }
-                        } else {
-                            if !*is_matched {
-                                *is_matched = matching_cache.check_condition_match(regex_condition);
+                                context_collector.add_matched_line(line_number);
+                                matched_lines[rule.number]
+                                    .push((line_number, get_line_span(&line, regex_condition)));
+                                if rule.is_explained {
+                                    record_matched_line(
+                                        &mut outcomes[content_condition_node.number],
+                                        line_number,
+                                    );
+                                }
                             }
-                            if *is_matched {
-                                num_satisfied_content_conditions += 1;
+                        } else if !*is_matched {
+                            *is_matched = check_sequenced_condition_match(
+                                &mut matching_cache,
+                                regex_condition,
+                                rule,
+                                &mut sequence_starts[content_condition_node.number],
+                                line_number,
+                            );
+                            if *is_matched && rule.is_explained {
+                                record_matched_line(
+                                    &mut outcomes[content_condition_node.number],
+                                    line_number,
+                                );
                             }
                         }
+                        // reporting target collects all matched lines, so it's
+                        // only settled when there are no more lines to check
+                        (*is_matched && !content_condition_node.is_reporting_target)
+                            || regex_condition.is_exhausted(line_number + 1)
                     }
-                    ContentCondition::Lines(size_condition) => {
-                        if size_condition.check_for_this_and_above(line_number) {
-                            num_satisfied_content_conditions += 1;
+                    ContentCondition::Lines(size_condition)
+                        if size_condition.check_for_this_and_above(line_number) =>
+                    {
+                        if rule.is_explained {
+                            outcomes[content_condition_node.number] =
+                                ContentConditionOutcome::AtLeast(line_number + 1);
                         }
+                        true
+                    }
+                    _ => false,
+                };
+
+                if is_settled {
+                    num_settled_content_conditions += 1;
+                    if rule.is_explained
+                        && outcomes[content_condition_node.number]
+                            == ContentConditionOutcome::NotChecked
+                    {
+                        outcomes[content_condition_node.number] =
+                            ContentConditionOutcome::NotMatched;
                     }
-                    _ => {}
                 }
             }
 
-            // we don't need to do any more checks if all conditions are already satisfied
-            num_satisfied_content_conditions != path_condition.linewise_content_conditions_count
+            // we don't need to do any more checks if all conditions are already settled
+            num_settled_content_conditions != path_condition.linewise_content_conditions_count
         });
 
         // interrupt processing this file if all condition statuses are already known
-        if rules_with_conditions.is_empty() {
+        // (but still read lines which follow last matched lines if these are needed,
+        // and lines which may contain ignore-file marker)
+        if rules_with_conditions.is_empty()
+            && !context_collector.has_pending_lines()
+            && line_number + 1 >= IGNORE_FILE_MARKER_MAX_LINES as u64
+        {
             break;
         }
 
+        context_collector.add_preceding_line(&line);
+
         line_number += 1;
     }
 
+    apply_match_count_conditions(
+        &rules_with_conditions_to_finalize,
+        &match_counts,
+        &mut local_condition_statuses,
+    );
+
+    apply_deferred_line_conditions(
+        line_number,
+        &mut rules_with_conditions,
+        &mut rules_with_conditions_to_finalize,
+        &mut deferred_matched_lines,
+        &mut local_condition_statuses,
+        &mut matched_lines,
+        outcomes,
+    );
+
     apply_file_line_conditions(
         line_number,
         &mut rules_with_conditions,
         &mut rules_with_conditions_to_finalize,
+        outcomes,
     );
 
     rules_with_conditions_to_finalize
@@ -152,7 +505,14 @@ fn apply_content_rules(
                 global_rule_statuses[rule.number].matched_lines.extend(
                     matched_lines[rule.number]
                         .iter()
-                        .map(|line_number| (path.clone(), *line_number)),
+                        .map(|(line_number, span)| {
+                            (
+                                path.clone(),
+                                *line_number,
+                                span.clone(),
+                                context_collector.get_context(*line_number),
+                            )
+                        }),
                 );
             }
 
@@ -163,7 +523,26 @@ fn apply_content_rules(
             }
         });
 
-    Ok(())
+    file_ignore_scopes.insert(path, file_ignore_scope);
+
+    // reading may have been interrupted early, so this is not always the file size
+    Ok(Some(reader.stream_position()?))
+}
+
+/// Collect rules suppressed by `ignore-file` markers at the start of the file
+///
+/// This is only needed for files which were not read while checking content
+/// conditions, as for the others the scope is recorded during the check
+fn read_file_ignore_scope(root: &Path, path: &Path) -> IgnoreScope {
+    let mut scope: IgnoreScope = Default::default();
+    if let Ok(file) = File::open(root.join(path)) {
+        BufReader::new(file.take(IGNORE_FILE_MARKER_MAX_BYTES))
+            .lines()
+            .take(IGNORE_FILE_MARKER_MAX_LINES)
+            .map_while(Result::ok)
+            .for_each(|line| scope.merge(IgnoreMarkers::parse(&line).file));
+    }
+    scope
 }
 
 #[derive(Default)]
@@ -171,17 +550,28 @@ fn apply_content_rules(
 struct RuleMatchStatus<'a> {
     pub content_checks: Vec<(&'a GlobCondition, Rc<PathBuf>)>,
     pub matched_files: Vec<Rc<PathBuf>>,
-    pub matched_lines: Vec<(Rc<PathBuf>, u64)>,
+    pub matched_lines: Vec<(Rc<PathBuf>, u64, LineSpan, Option<LineContext>)>,
 }
 
-pub fn apply_ruleset<'a>(ruleset: &'a CompiledRuleset, root: &'a Path) -> MatchResult<'a> {
+pub fn apply_ruleset<'a>(
+    ruleset: &'a CompiledRuleset,
+    root: &'a Path,
+    context_lines: ContextLines,
+) -> MatchResult<'a> {
     let mut result: MatchResult = Default::default();
+    result.stats.roots_scanned = 1;
 
     let mut rule_statuses: Vec<RuleMatchStatus> =
         ruleset.rules.iter().map(|_| Default::default()).collect();
 
     let mut files_condition_statuses: Vec<bool> = vec![false; ruleset.conditions_count];
 
+    let mut explanations: Vec<Option<Explanation>> = ruleset
+        .rules
+        .iter()
+        .map(|rule| rule.is_explained.then(|| Explanation::new(rule, root)))
+        .collect();
+
     let mut rules: Vec<_> = ruleset.rules.iter().collect();
 
     rules.retain(|rule| {
@@ -190,6 +580,9 @@ pub fn apply_ruleset<'a>(ruleset: &'a CompiledRuleset, root: &'a Path) -> MatchR
         if rule.path_conditions.is_empty() {
             // rules without any glob matchers always match on the root level
             result.matches.push(Match::for_root(rule, root));
+            if let Some(explanation) = &mut explanations[rule.number] {
+                explanation.is_matched = true;
+            }
             return false;
         }
         true
@@ -205,11 +598,18 @@ pub fn apply_ruleset<'a>(ruleset: &'a CompiledRuleset, root: &'a Path) -> MatchR
         .filter(|e| e.file_type().is_file())
         .map(|e| Rc::new(e.into_path().strip_prefix(root).unwrap().to_path_buf()))
         .for_each(|path| {
+            result.stats.files_walked += 1;
             let mut matching_cache =
                 GlobMatchingCache::new(&path, match_options, ruleset.globs_count);
 
             rules.retain(|rule| {
                 for path_condition in &rule.path_conditions {
+                    if let Some(explanation) = &mut explanations[rule.number]
+                        && matching_cache.check_condition_match(path_condition)
+                    {
+                        explanation.add_matched_path(path_condition.number, &path);
+                    }
+
                     match path_condition.logic {
                         ConditionLogic::Negative => {
                             if matching_cache.check_condition_match(path_condition) {
@@ -263,16 +663,72 @@ pub fn apply_ruleset<'a>(ruleset: &'a CompiledRuleset, root: &'a Path) -> MatchR
         true
     });
 
+    // ignore-file markers are recorded for files read while checking their
+    // content, and are only looked for in other files if these are reported
+    let mut file_ignore_scopes: HashMap<Rc<PathBuf>, IgnoreScope> = HashMap::new();
+
     for (path, rules_with_conditions) in content_rules_by_path.into_iter() {
-        if let Err(err) = apply_content_rules(
+        let explained_rules_with_conditions: Vec<(&Rule, &GlobCondition)> = rules_with_conditions
+            .iter()
+            .filter(|(rule, _)| rule.is_explained)
+            .copied()
+            .collect();
+
+        // outcomes of content conditions are only tracked for explained rules
+        let mut outcomes: Vec<ContentConditionOutcome> =
+            if explained_rules_with_conditions.is_empty() {
+                vec![]
+            } else {
+                vec![Default::default(); ruleset.conditions_count]
+            };
+
+        // condition statuses are accumulated over all files; for explained
+        // conditions, reset them temporarily to find out the status for this file
+        let saved_condition_statuses: Vec<bool> = explained_rules_with_conditions
+            .iter()
+            .map(|(_, condition)| std::mem::take(&mut files_condition_statuses[condition.number]))
+            .collect();
+
+        match apply_content_rules(
             ruleset,
             root,
             path.clone(),
             rules_with_conditions,
             &mut rule_statuses,
             &mut files_condition_statuses,
+            &mut outcomes,
+            context_lines,
+            &mut file_ignore_scopes,
         ) {
-            eprintln!("failed to process {}: {}", path.display(), err);
+            Ok(Some(bytes_read)) => {
+                result.stats.files_read += 1;
+                result.stats.bytes_read += bytes_read;
+            }
+            Ok(None) => {}
+            Err(err) => eprintln!("failed to process {}: {}", path.display(), err),
+        }
+
+        for ((rule, condition), saved_condition_status) in explained_rules_with_conditions
+            .into_iter()
+            .zip(saved_condition_statuses)
+        {
+            let is_matched = files_condition_statuses[condition.number];
+            files_condition_statuses[condition.number] |= saved_condition_status;
+
+            if let Some(explanation) = &mut explanations[rule.number] {
+                explanation
+                    .get_path_condition_trace(condition.number)
+                    .files
+                    .push(FileTrace {
+                        path: path.clone(),
+                        outcomes: condition
+                            .content_conditions
+                            .iter()
+                            .map(|node| outcomes[node.number].clone())
+                            .collect(),
+                        is_matched,
+                    });
+            }
         }
     }
 
@@ -281,23 +737,51 @@ pub fn apply_ruleset<'a>(ruleset: &'a CompiledRuleset, root: &'a Path) -> MatchR
             return;
         }
 
+        if let Some(explanation) = &mut explanations[rule.number] {
+            explanation.is_matched = true;
+        }
+
         if rule.is_reporting_target {
             result.matches.push(Match::for_root(rule, root));
         }
 
         let rule_status = &rule_statuses[rule.number];
 
+        let mut is_ignored_for_file = |path: &Rc<PathBuf>| {
+            file_ignore_scopes
+                .entry(path.clone())
+                .or_insert_with(|| read_file_ignore_scope(root, path))
+                .applies_to(rule)
+        };
+
         for path in &rule_status.matched_files {
-            result
-                .matches
-                .push(Match::for_file(rule, root, path.clone()))
+            if !is_ignored_for_file(path) {
+                result
+                    .matches
+                    .push(Match::for_file(rule, root, path.clone()))
+            }
         }
-        for (path, line_number) in &rule_status.matched_lines {
-            result
-                .matches
-                .push(Match::for_line(rule, root, path.clone(), *line_number))
+        for (path, line_number, span, context) in &rule_status.matched_lines {
+            if !is_ignored_for_file(path) {
+                result.matches.push(Match::for_line(
+                    rule,
+                    root,
+                    path.clone(),
+                    *line_number,
+                    span.clone(),
+                    context.clone(),
+                ))
+            }
         }
     });
 
+    result
+        .explanations
+        .extend(explanations.into_iter().flatten());
+
+    let mut ignore_file = IgnoreFile::load(root);
+    result.matches.retain(|m| !ignore_file.suppresses(m));
+    result.unused_ignore_entries = ignore_file.get_unused_entries(&ruleset.rules);
+
     result
 }
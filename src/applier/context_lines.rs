@@ -0,0 +1,143 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::r#match::{LineContext, truncate_line};
+use std::collections::{HashMap, VecDeque};
+
+/// Numbers of lines to collect around matched lines
+#[derive(Default, Clone, Copy)]
+#[cfg_attr(not(feature = "coverage"), derive(Debug))]
+pub struct ContextLines {
+    pub before: usize,
+    pub after: usize,
+}
+
+impl ContextLines {
+    fn is_enabled(&self) -> bool {
+        self.before > 0 || self.after > 0
+    }
+}
+
+/// Collects lines surrounding matched lines while the file is read
+///
+/// Only a small ring buffer of preceding lines is kept, and following
+/// lines are appended to contexts of recent matches as they are read,
+/// so the file does not need to be read again
+pub struct ContextCollector {
+    context_lines: ContextLines,
+    preceding_lines: VecDeque<String>,
+    pending_line_numbers: Vec<u64>,
+    contexts: HashMap<u64, LineContext>,
+}
+
+impl ContextCollector {
+    pub fn new(context_lines: ContextLines) -> Self {
+        Self {
+            context_lines,
+            preceding_lines: VecDeque::with_capacity(context_lines.before),
+            pending_line_numbers: vec![],
+            contexts: HashMap::new(),
+        }
+    }
+
+    /// Add line which is about to be checked to contexts of preceding matched lines
+    pub fn add_following_line(&mut self, line: &str) {
+        let contexts = &mut self.contexts;
+        let after = self.context_lines.after;
+        self.pending_line_numbers.retain(|line_number| {
+            let context = contexts.get_mut(line_number).unwrap();
+            context.after.push(truncate_line(line).to_owned());
+            context.after.len() < after
+        });
+    }
+
+    /// Remember line which was just checked as possible context of following matched lines
+    pub fn add_preceding_line(&mut self, line: &str) {
+        if self.context_lines.before == 0 {
+            return;
+        }
+        if self.preceding_lines.len() == self.context_lines.before {
+            self.preceding_lines.pop_front();
+        }
+        self.preceding_lines
+            .push_back(truncate_line(line).to_owned());
+    }
+
+    /// Record context of the line which is currently being checked
+    pub fn add_matched_line(&mut self, line_number: u64) {
+        if !self.context_lines.is_enabled() || self.contexts.contains_key(&line_number) {
+            return;
+        }
+        self.contexts.insert(
+            line_number,
+            LineContext {
+                before: self.preceding_lines.iter().cloned().collect(),
+                after: vec![],
+            },
+        );
+        if self.context_lines.after > 0 {
+            self.pending_line_numbers.push(line_number);
+        }
+    }
+
+    /// Whether more lines are needed to complete contexts of already matched lines
+    pub fn has_pending_lines(&self) -> bool {
+        !self.pending_line_numbers.is_empty()
+    }
+
+    pub fn get_context(&self, line_number: u64) -> Option<LineContext> {
+        self.contexts.get(&line_number).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(context_lines: ContextLines, lines: &[&str], matched: &[u64]) -> ContextCollector {
+        let mut collector = ContextCollector::new(context_lines);
+        for (line_number, line) in lines.iter().enumerate() {
+            collector.add_following_line(line);
+            if matched.contains(&(line_number as u64)) {
+                collector.add_matched_line(line_number as u64);
+            }
+            collector.add_preceding_line(line);
+        }
+        collector
+    }
+
+    #[test]
+    fn disabled() {
+        let collector = collect(Default::default(), &["a", "b"], &[0]);
+        assert!(collector.get_context(0).is_none());
+    }
+
+    #[test]
+    fn before_and_after() {
+        let context_lines = ContextLines {
+            before: 2,
+            after: 1,
+        };
+        let collector = collect(context_lines, &["a", "b", "c", "d", "e"], &[3]);
+        let context = collector.get_context(3).unwrap();
+        assert_eq!(context.before, vec!["b", "c"]);
+        assert_eq!(context.after, vec!["e"]);
+        assert!(!collector.has_pending_lines());
+    }
+
+    #[test]
+    fn file_boundaries() {
+        let context_lines = ContextLines {
+            before: 2,
+            after: 2,
+        };
+        let collector = collect(context_lines, &["a", "b"], &[0, 1]);
+        let context = collector.get_context(0).unwrap();
+        assert!(context.before.is_empty());
+        assert_eq!(context.after, vec!["b"]);
+        let context = collector.get_context(1).unwrap();
+        assert_eq!(context.before, vec!["a"]);
+        assert!(context.after.is_empty());
+        assert!(collector.has_pending_lines());
+    }
+}
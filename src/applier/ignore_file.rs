@@ -0,0 +1,208 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::r#match::Match;
+use crate::ruleset::{Glob, Rule};
+use std::path::{Path, PathBuf};
+
+pub const IGNORE_FILE_NAME: &str = ".omnilinterignore";
+
+#[cfg_attr(not(feature = "coverage"), derive(Debug))]
+struct IgnoreFileEntry {
+    /// Rule id or tag, or `*` for all rules
+    rule: String,
+    path: Glob,
+    /// 1-based line number
+    line: Option<u64>,
+    source_line_number: usize,
+    is_used: bool,
+}
+
+impl IgnoreFileEntry {
+    fn parse(text: &str) -> Result<Self, String> {
+        let (rule, path) = text
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| "expected rule id and path pattern".to_owned())?;
+        let path = path.trim();
+
+        let (path, line) = match path.rsplit_once(':') {
+            Some((path, line)) if !line.is_empty() && line.bytes().all(|c| c.is_ascii_digit()) => {
+                match line.parse::<u64>() {
+                    Ok(line) if line > 0 => (path, Some(line)),
+                    _ => return Err(format!("bad line number \"{line}\"")),
+                }
+            }
+            _ => (path, None),
+        };
+
+        Ok(Self {
+            rule: rule.to_lowercase(),
+            path: Glob::new(path).map_err(|err| err.to_string())?,
+            line,
+            source_line_number: 0,
+            is_used: false,
+        })
+    }
+
+    /// Whether the entry may suppress matches of any of the given rules
+    fn applies_to_any_rule(&self, rules: &[Rule]) -> bool {
+        rules.iter().any(|rule| self.applies_to_rule(rule))
+    }
+
+    fn applies_to_rule(&self, rule: &Rule) -> bool {
+        self.rule == "*" || rule.id.as_ref() == Some(&self.rule) || rule.tags.contains(&self.rule)
+    }
+
+    fn suppresses(&self, m: &Match) -> bool {
+        let Some(file) = &m.file else {
+            return false;
+        };
+
+        let mut match_options = glob::MatchOptions::new();
+        match_options.require_literal_separator = true;
+
+        self.applies_to_rule(m.rule)
+            && self.path.matches_path_with(&file.path, match_options)
+            && (self.line.is_none() || self.line == file.line.map(|line| line + 1))
+    }
+}
+
+/// Suppressions listed in the `.omnilinterignore` file of a root
+#[derive(Default)]
+#[cfg_attr(not(feature = "coverage"), derive(Debug))]
+pub struct IgnoreFile {
+    path: PathBuf,
+    entries: Vec<IgnoreFileEntry>,
+}
+
+impl IgnoreFile {
+    /// Load the ignore file from the given root, if it's present
+    ///
+    /// Problems with the file are reported as warnings, as they should
+    /// not prevent the root from being checked
+    pub fn load(root: &Path) -> Self {
+        let path = root.join(IGNORE_FILE_NAME);
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Default::default(),
+            Err(err) => {
+                eprintln!("Warning: cannot read {}: {}", path.display(), err);
+                return Default::default();
+            }
+        };
+
+        let entries = text
+            .lines()
+            .enumerate()
+            .filter_map(|(line_index, line)| {
+                let line = line.split_once('#').map_or(line, |(line, _)| line).trim();
+                if line.is_empty() {
+                    return None;
+                }
+                match IgnoreFileEntry::parse(line) {
+                    Ok(entry) => Some(IgnoreFileEntry {
+                        source_line_number: line_index + 1,
+                        ..entry
+                    }),
+                    Err(err) => {
+                        eprintln!(
+                            "Warning: {}:{}: skipping invalid entry: {}",
+                            path.display(),
+                            line_index + 1,
+                            err
+                        );
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        Self { path, entries }
+    }
+
+    /// Check whether the match is suppressed by any of the entries
+    ///
+    /// All matching entries are marked as used, so none of them are
+    /// reported as stale
+    pub fn suppresses(&mut self, m: &Match) -> bool {
+        let mut is_suppressed = false;
+        for entry in &mut self.entries {
+            if entry.suppresses(m) {
+                entry.is_used = true;
+                is_suppressed = true;
+            }
+        }
+        is_suppressed
+    }
+
+    /// Get entries which did not suppress anything
+    ///
+    /// Entries for rules which are not checked (for instance, because these
+    /// were skipped with `--tags`) cannot suppress anything, so these are
+    /// not considered stale
+    pub fn get_unused_entries(&self, rules: &[Rule]) -> Vec<UnusedIgnoreEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| !entry.is_used && entry.applies_to_any_rule(rules))
+            .map(|entry| UnusedIgnoreEntry {
+                path: self.path.clone(),
+                line_number: entry.source_line_number,
+            })
+            .collect()
+    }
+}
+
+/// Ignore file entry which did not suppress anything
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(not(feature = "coverage"), derive(Debug))]
+pub struct UnusedIgnoreEntry {
+    path: PathBuf,
+    /// 1-based line number in the ignore file
+    line_number: usize,
+}
+
+/// Report unused entries of ignore files of all roots in a stable order
+pub fn warn_about_unused_ignore_entries(entries: &mut [UnusedIgnoreEntry]) {
+    entries.sort();
+    for entry in entries {
+        eprintln!(
+            "Warning: {}:{}: entry does not match anything and may be removed",
+            entry.path.display(),
+            entry.line_number
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_entry() {
+        let entry = IgnoreFileEntry::parse("Rule-1 src/*.py").unwrap();
+        assert_eq!(entry.rule, "rule-1");
+        assert_eq!(entry.path.as_str(), "src/*.py");
+        assert_eq!(entry.line, None);
+    }
+
+    #[test]
+    fn parse_entry_with_line() {
+        let entry = IgnoreFileEntry::parse("rule-1   src/a.py:10").unwrap();
+        assert_eq!(entry.path.as_str(), "src/a.py");
+        assert_eq!(entry.line, Some(10));
+    }
+
+    #[test]
+    fn parse_entry_with_colon_in_path() {
+        let entry = IgnoreFileEntry::parse("rule-1 a:b.py").unwrap();
+        assert_eq!(entry.path.as_str(), "a:b.py");
+        assert_eq!(entry.line, None);
+    }
+
+    #[test]
+    fn parse_bad_entries() {
+        assert!(IgnoreFileEntry::parse("rule-1").is_err());
+        assert!(IgnoreFileEntry::parse("rule-1 a.py:0").is_err());
+        assert!(IgnoreFileEntry::parse("rule-1 [a.py").is_err());
+    }
+}
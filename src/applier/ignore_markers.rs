@@ -0,0 +1,232 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::ruleset::Rule;
+
+const MARKER_PREFIX: &str = "omnilinter: ";
+
+/// Number of lines at the start of a file in which `ignore-file` marker is looked for
+pub const IGNORE_FILE_MARKER_MAX_LINES: usize = 10;
+
+/// Limit on data read when looking for `ignore-file` marker in files
+/// which were not otherwise read, as these may be binary or huge
+pub const IGNORE_FILE_MARKER_MAX_BYTES: u64 = 64 * 1024;
+
+/// Set of rules suppressed by ignore markers
+#[derive(Default, Clone)]
+#[cfg_attr(not(feature = "coverage"), derive(Debug))]
+pub enum IgnoreScope {
+    #[default]
+    None,
+    All,
+    /// Rules with given ids or tags
+    Rules(Vec<String>),
+}
+
+impl IgnoreScope {
+    pub fn merge(&mut self, other: IgnoreScope) {
+        match (&mut *self, other) {
+            (IgnoreScope::All, _) | (_, IgnoreScope::None) => {}
+            (IgnoreScope::Rules(names), IgnoreScope::Rules(other_names)) => {
+                names.extend(other_names)
+            }
+            (_, other) => *self = other,
+        }
+    }
+
+    /// Remove rules from the scope
+    ///
+    /// Note that specific rules cannot be removed from the scope which
+    /// includes all rules, so these are only removed when listed explicitly
+    pub fn subtract(&mut self, other: &IgnoreScope) {
+        match (&mut *self, other) {
+            (_, IgnoreScope::All) => *self = IgnoreScope::None,
+            (IgnoreScope::Rules(names), IgnoreScope::Rules(other_names)) => {
+                names.retain(|name| !other_names.contains(name));
+                if names.is_empty() {
+                    *self = IgnoreScope::None;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn applies_to(&self, rule: &Rule) -> bool {
+        match self {
+            IgnoreScope::None => false,
+            IgnoreScope::All => true,
+            IgnoreScope::Rules(names) => names
+                .iter()
+                .any(|name| rule.id.as_ref() == Some(name) || rule.tags.contains(name)),
+        }
+    }
+}
+
+/// Ignore markers found in a single line
+#[derive(Default)]
+#[cfg_attr(not(feature = "coverage"), derive(Debug))]
+pub struct IgnoreMarkers {
+    pub this_line: IgnoreScope,
+    pub next_line: IgnoreScope,
+    pub disable: IgnoreScope,
+    pub enable: IgnoreScope,
+    pub file: IgnoreScope,
+}
+
+fn parse_rule_names(text: &str) -> Option<(Vec<String>, &str)> {
+    let (names, rest) = text.strip_prefix('[')?.split_once(']')?;
+    Some((
+        names
+            .split(',')
+            .map(|name| name.trim().to_lowercase())
+            .filter(|name| !name.is_empty())
+            .collect(),
+        rest,
+    ))
+}
+
+impl IgnoreMarkers {
+    /// Check whether the line may contain any markers, which is much
+    /// cheaper than parsing it, as most lines do not contain these
+    pub fn may_be_present(line: &str) -> bool {
+        line.contains(MARKER_PREFIX)
+    }
+
+    /// Parse all `omnilinter: <marker>[[ids-or-tags]]` markers in a line
+    pub fn parse(line: &str) -> Self {
+        let mut markers: IgnoreMarkers = Default::default();
+        let mut rest = line;
+
+        while let Some(pos) = rest.find(MARKER_PREFIX) {
+            rest = &rest[pos + MARKER_PREFIX.len()..];
+
+            let keyword_length = rest
+                .find(|c: char| c != '-' && !c.is_alphanumeric())
+                .unwrap_or(rest.len());
+            let target = match &rest[..keyword_length] {
+                "ignore" => &mut markers.this_line,
+                "ignore-next-line" => &mut markers.next_line,
+                "ignore-file" => &mut markers.file,
+                "disable" => &mut markers.disable,
+                "enable" => &mut markers.enable,
+                // any marker starting with `ignore` used to suppress the line,
+                // so unknown variants (e.g. `ignored`) keep doing that
+                keyword if keyword.starts_with("ignore") => &mut markers.this_line,
+                _ => continue,
+            };
+            rest = &rest[keyword_length..];
+
+            let scope = if let Some((names, after_names)) = parse_rule_names(rest) {
+                rest = after_names;
+                IgnoreScope::Rules(names)
+            } else {
+                IgnoreScope::All
+            };
+
+            target.merge(scope);
+        }
+
+        markers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(id: &str, tags: &[&str]) -> Rule {
+        Rule {
+            id: Some(id.to_owned()),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn no_markers() {
+        assert!(!IgnoreMarkers::may_be_present("foo bar"));
+        let markers = IgnoreMarkers::parse("foo bar");
+        assert!(matches!(markers.this_line, IgnoreScope::None));
+        assert!(matches!(markers.next_line, IgnoreScope::None));
+    }
+
+    #[test]
+    fn bare_marker() {
+        assert!(IgnoreMarkers::may_be_present("foo # omnilinter: ignore"));
+        let markers = IgnoreMarkers::parse("foo # omnilinter: ignore");
+        assert!(matches!(markers.this_line, IgnoreScope::All));
+        assert!(matches!(markers.next_line, IgnoreScope::None));
+    }
+
+    #[test]
+    fn rule_names() {
+        let scope = IgnoreMarkers::parse("foo # omnilinter: ignore[a, B,c]").this_line;
+        assert!(scope.applies_to(&rule("a", &[])));
+        assert!(scope.applies_to(&rule("b", &[])));
+        assert!(scope.applies_to(&rule("x", &["c"])));
+        assert!(!scope.applies_to(&rule("d", &["e"])));
+    }
+
+    #[test]
+    fn next_line() {
+        let markers = IgnoreMarkers::parse("# omnilinter: ignore-next-line[a]");
+        assert!(matches!(markers.this_line, IgnoreScope::None));
+        assert!(markers.next_line.applies_to(&rule("a", &[])));
+        assert!(!markers.next_line.applies_to(&rule("b", &[])));
+    }
+
+    #[test]
+    fn multiple_markers() {
+        let markers = IgnoreMarkers::parse(
+            "omnilinter: ignore[a] omnilinter: ignore[b] omnilinter: ignore-next-line",
+        );
+        assert!(markers.this_line.applies_to(&rule("a", &[])));
+        assert!(markers.this_line.applies_to(&rule("b", &[])));
+        assert!(!markers.this_line.applies_to(&rule("c", &[])));
+        assert!(matches!(markers.next_line, IgnoreScope::All));
+    }
+
+    #[test]
+    fn other_markers() {
+        let markers = IgnoreMarkers::parse("omnilinter: foo omnilinter: ignore-file");
+        assert!(matches!(markers.this_line, IgnoreScope::None));
+        assert!(matches!(markers.next_line, IgnoreScope::None));
+        assert!(matches!(markers.file, IgnoreScope::All));
+    }
+
+    #[test]
+    fn unknown_ignore_markers() {
+        for line in [
+            "omnilinter: ignored",
+            "omnilinter: ignore-this",
+            "omnilinter: ignorefoo",
+        ] {
+            let markers = IgnoreMarkers::parse(line);
+            assert!(matches!(markers.this_line, IgnoreScope::All));
+            assert!(matches!(markers.next_line, IgnoreScope::None));
+        }
+    }
+
+    #[test]
+    fn block_markers() {
+        let markers = IgnoreMarkers::parse("omnilinter: disable[a] omnilinter: enable");
+        assert!(markers.disable.applies_to(&rule("a", &[])));
+        assert!(matches!(markers.enable, IgnoreScope::All));
+    }
+
+    #[test]
+    fn subtract() {
+        let mut scope = IgnoreScope::Rules(vec!["a".to_owned(), "b".to_owned()]);
+        scope.subtract(&IgnoreScope::Rules(vec!["a".to_owned()]));
+        assert!(!scope.applies_to(&rule("a", &[])));
+        assert!(scope.applies_to(&rule("b", &[])));
+        scope.subtract(&IgnoreScope::Rules(vec!["b".to_owned()]));
+        assert!(matches!(scope, IgnoreScope::None));
+
+        let mut scope = IgnoreScope::All;
+        scope.subtract(&IgnoreScope::Rules(vec!["a".to_owned()]));
+        assert!(matches!(scope, IgnoreScope::All));
+        scope.subtract(&IgnoreScope::All);
+        assert!(matches!(scope, IgnoreScope::None));
+    }
+}
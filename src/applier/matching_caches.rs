@@ -1,7 +1,8 @@
 // SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-use crate::ruleset::{Glob, GlobCondition, Regex, RegexCondition};
+use super::ignore_markers::IgnoreScope;
+use crate::ruleset::{Glob, GlobCondition, Regex, RegexCondition, Rule};
 use std::path::Path;
 
 pub struct GlobMatchingCache<'a> {
@@ -52,24 +53,24 @@ impl<'a> GlobMatchingCache<'a> {
 
 pub struct RegexMatchingCache<'a> {
     line: &'a str,
+    ignore_scope: &'a IgnoreScope,
     #[cfg_attr(not(feature = "matching-cache"), allow(dead_code))]
     cached_matches: Vec<Option<bool>>,
 }
 
-const IGNORE_MARKER: &str = "omnilinter: ignore";
-
 impl<'a> RegexMatchingCache<'a> {
-    pub fn new(line: &'a str, size: usize) -> Self {
+    pub fn new(line: &'a str, ignore_scope: &'a IgnoreScope, size: usize) -> Self {
         RegexMatchingCache {
             line,
-            cached_matches: vec![None; size + 1],
+            ignore_scope,
+            cached_matches: vec![None; size],
         }
     }
 
     pub fn check_pattern_match(&mut self, regex: &Regex) -> bool {
         #[cfg(feature = "matching-cache")]
         {
-            let cached = &mut self.cached_matches[regex.get_unique_id() + 1];
+            let cached = &mut self.cached_matches[regex.get_unique_id()];
             if let Some(cached) = &cached {
                 *cached
             } else {
@@ -84,33 +85,15 @@ impl<'a> RegexMatchingCache<'a> {
         }
     }
 
-    pub fn check_ignore_marker_match(&mut self) -> bool {
-        #[cfg(feature = "matching-cache")]
-        {
-            let cached = &mut self.cached_matches[0];
-            if let Some(cached) = &cached {
-                *cached
-            } else {
-                let computed = self.line.contains(IGNORE_MARKER);
-                *cached = Some(computed);
-                computed
-            }
-        }
-        #[cfg(not(feature = "matching-cache"))]
-        {
-            self.line.contains(IGNORE_MARKER)
-        }
-    }
-
-    pub fn check_condition_match(&mut self, condition: &RegexCondition) -> bool {
-        condition
-            .patterns
-            .iter()
-            .any(|regex| self.check_pattern_match(regex))
+    pub fn check_condition_match(&mut self, condition: &RegexCondition, rule: &Rule) -> bool {
+        !self.ignore_scope.applies_to(rule)
+            && condition
+                .patterns
+                .iter()
+                .any(|regex| self.check_pattern_match(regex))
             && !condition
                 .excludes
                 .iter()
                 .any(|regex| self.check_pattern_match(regex))
-            && !self.check_ignore_marker_match()
     }
 }
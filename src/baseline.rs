@@ -0,0 +1,148 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::fingerprint::get_line_fingerprint;
+use crate::r#match::{Match, MatchResult};
+use anyhow::{Context, Error};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const BASELINE_VERSION: u32 = 1;
+
+/// Recorded match, identified by its content rather than line
+/// number, so it survives lines being shifted around
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(not(feature = "coverage"), derive(Debug))]
+struct BaselineEntry {
+    /// Rule id, or title for rules without id
+    rule: String,
+    root: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fingerprint: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BaselineFile {
+    version: u32,
+    matches: Vec<BaselineEntry>,
+}
+
+/// Converter of roots into a form independent of how these were specified
+///
+/// Roots are made relative to the directory of the baseline file, so e.g.
+/// `./repo`, `repo/` and `/abs/path/repo` are all recorded as `repo`
+struct RootNormalizer<'a> {
+    base_dir: PathBuf,
+    normalized_roots: HashMap<&'a Path, String>,
+}
+
+impl<'a> RootNormalizer<'a> {
+    fn new(baseline_path: &Path) -> Self {
+        let base_dir = baseline_path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        Self {
+            base_dir: base_dir
+                .canonicalize()
+                .unwrap_or_else(|_| base_dir.to_path_buf()),
+            normalized_roots: HashMap::new(),
+        }
+    }
+
+    fn normalize(&mut self, root: &'a Path) -> &str {
+        self.normalized_roots.entry(root).or_insert_with(|| {
+            let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+            match root.strip_prefix(&self.base_dir) {
+                Ok(relative) if relative.as_os_str().is_empty() => ".".to_owned(),
+                Ok(relative) => relative.display().to_string(),
+                Err(_) => root.display().to_string(),
+            }
+        })
+    }
+}
+
+impl BaselineEntry {
+    fn for_match<'a>(m: &Match<'a>, root_normalizer: &mut RootNormalizer<'a>) -> Self {
+        let file = m.file.as_ref();
+        Self {
+            rule: m.rule.id.clone().unwrap_or_else(|| m.rule.title.clone()),
+            root: root_normalizer.normalize(m.root).to_owned(),
+            path: file.map(|file| file.path.display().to_string()),
+            fingerprint: get_line_fingerprint(m).map(|fingerprint| format!("{fingerprint:016x}")),
+        }
+    }
+}
+
+pub fn write_baseline(match_result: &MatchResult, path: &Path) -> Result<(), Error> {
+    let mut root_normalizer = RootNormalizer::new(path);
+    let mut matches: Vec<BaselineEntry> = match_result
+        .matches
+        .iter()
+        .map(|m| BaselineEntry::for_match(m, &mut root_normalizer))
+        .collect();
+    // keep the file stable regardless of the order roots were processed in
+    matches.sort();
+    let baseline = BaselineFile {
+        version: BASELINE_VERSION,
+        matches,
+    };
+    let mut text = serde_json::to_string_pretty(&baseline)?;
+    text.push('\n');
+    std::fs::write(path, text)
+        .with_context(|| format!("cannot write baseline file {}", path.display()))
+}
+
+/// Previously recorded matches which should not be reported
+pub struct Baseline {
+    path: PathBuf,
+    remaining_counts: HashMap<BaselineEntry, usize>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read baseline file {}", path.display()))?;
+        let baseline: BaselineFile = serde_json::from_str(&text)
+            .with_context(|| format!("cannot parse baseline file {}", path.display()))?;
+        if baseline.version != BASELINE_VERSION {
+            anyhow::bail!(
+                "unsupported baseline file version {} in {}",
+                baseline.version,
+                path.display()
+            );
+        }
+
+        let mut remaining_counts: HashMap<BaselineEntry, usize> = HashMap::new();
+        for entry in baseline.matches {
+            *remaining_counts.entry(entry).or_default() += 1;
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            remaining_counts,
+        })
+    }
+
+    /// Remove matches recorded in the baseline from the result
+    ///
+    /// Each recorded entry suppresses at most one match, so new
+    /// occurrences of an already known problem are still reported
+    pub fn apply(&mut self, match_result: &mut MatchResult) {
+        let mut root_normalizer = RootNormalizer::new(&self.path);
+        match_result.matches.retain(|m| {
+            match self
+                .remaining_counts
+                .get_mut(&BaselineEntry::for_match(m, &mut root_normalizer))
+            {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            }
+        });
+    }
+}
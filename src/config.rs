@@ -1,7 +1,8 @@
 // SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-mod dumper;
+pub mod dumper;
+mod json_dumper;
 mod parser;
 
 use crate::ruleset::Ruleset;
@@ -3,94 +3,131 @@
 
 use crate::config::Config;
 use crate::ruleset::{
-    ConditionLogic, ContentCondition, ContentConditionNode, Glob, GlobCondition, Regex,
+    ConditionLogic, ContentCondition, ContentConditionNode, Glob, GlobCondition, LineRange, Regex,
     RegexCondition, Rule, SizeCondition, SizeOperator,
 };
 
-fn dump_glob(glob: &Glob) {
-    print!("{}", glob.as_str());
+fn format_glob(glob: &Glob) -> String {
+    glob.as_str().to_owned()
 }
 
 const REGEX_FRAMING_CHARACTERS: &str = "/\"'|#%*\\";
 
-fn dump_regex(regex: &Regex) {
+fn format_regex(regex: &Regex) -> String {
     let regex = regex.as_str();
     for framing_character in REGEX_FRAMING_CHARACTERS.chars() {
         if !regex.contains(framing_character) {
-            print!("{framing_character}{regex}{framing_character}");
-            return;
+            return format!("{framing_character}{regex}{framing_character}");
         }
     }
     panic!("unable to dump regex {regex}, could not find suitable framing character");
 }
 
-fn dump_regex_condition_args(regex_condition: &RegexCondition) {
+fn format_regex_condition_args(regex_condition: &RegexCondition) -> String {
+    let mut res = String::new();
     regex_condition.patterns.iter().for_each(|regex| {
-        print!(" ");
-        dump_regex(regex);
+        res += " ";
+        res += &format_regex(regex);
     });
     regex_condition.excludes.iter().for_each(|regex| {
-        print!(" !");
-        dump_regex(regex);
+        res += " !";
+        res += &format_regex(regex);
     });
+    match regex_condition.line_range {
+        Some(LineRange::First(count)) => res += &format!(" within first {count} lines"),
+        Some(LineRange::Last(count)) => res += &format!(" within last {count} lines"),
+        Some(LineRange::Span(first, last)) => res += &format!(" within lines {first}..{last}"),
+        None => {}
+    }
+    if let Some(follow_up) = &regex_condition.follow_up {
+        res += " then";
+        res += &format_regex_condition_args(&follow_up.condition);
+        if let Some(max_distance) = follow_up.max_distance {
+            res += &format!(" within {max_distance} lines");
+        }
+    }
+    res
 }
 
-fn dump_size_condition_args(size_condition: &SizeCondition) {
-    print!(
-        " {} {}",
-        match size_condition.operator {
-            SizeOperator::GreaterEqual => ">=",
-            SizeOperator::Greater => ">",
-            SizeOperator::LessEqual => "<=",
-            SizeOperator::Less => "<",
-            SizeOperator::Equal => "==",
-            SizeOperator::NotEqual => "!=",
-        },
-        size_condition.value
-    );
+fn format_size_condition_args(size_condition: &SizeCondition) -> String {
+    let operator = match size_condition.operator {
+        SizeOperator::GreaterEqual => ">=",
+        SizeOperator::Greater => ">",
+        SizeOperator::LessEqual => "<=",
+        SizeOperator::Less => "<",
+        SizeOperator::Equal => "==",
+        SizeOperator::NotEqual => "!=",
+        SizeOperator::Range(upper_value) => {
+            return format!(" {}..{}", size_condition.value, upper_value);
+        }
+    };
+    format!(" {} {}", operator, size_condition.value)
 }
 
-fn dump_content_condition(content_condition_node: &ContentConditionNode) {
+/// Format content condition the way it's written in the config
+pub fn format_content_condition(content_condition_node: &ContentConditionNode) -> String {
     match &content_condition_node.condition {
         ContentCondition::Match(regex_condition) => {
-            print!("        match");
-            dump_regex_condition_args(regex_condition);
+            "match".to_owned() + &format_regex_condition_args(regex_condition)
         }
         ContentCondition::NoMatch(regex_condition) => {
-            print!("        nomatch");
-            dump_regex_condition_args(regex_condition);
+            "nomatch".to_owned() + &format_regex_condition_args(regex_condition)
         }
         ContentCondition::Size(size_condition) => {
-            print!("        size");
-            dump_size_condition_args(size_condition);
+            "size".to_owned() + &format_size_condition_args(size_condition)
         }
         ContentCondition::Lines(size_condition) => {
-            print!("        lines");
-            dump_size_condition_args(size_condition);
+            "lines".to_owned() + &format_size_condition_args(size_condition)
         }
     }
-    println!();
 }
 
-fn dump_path_condition(path_condition: &GlobCondition) {
-    let directive = match path_condition.logic {
+/// Format match count condition which follows `match` condition
+pub fn format_match_count_condition(size_condition: &SizeCondition) -> String {
+    "matches".to_owned() + &format_size_condition_args(size_condition)
+}
+
+/// Format path condition the way it's written in the config, without
+/// the following content conditions
+pub fn format_path_condition(path_condition: &GlobCondition) -> String {
+    let mut res = match path_condition.logic {
         ConditionLogic::Positive => "files",
         ConditionLogic::Negative => "nofiles",
-    };
-    print!("    {directive}");
+    }
+    .to_owned();
     path_condition.patterns.iter().for_each(|glob| {
-        print!(" ");
-        dump_glob(glob);
+        res += " ";
+        res += &format_glob(glob);
     });
     path_condition.excludes.iter().for_each(|glob| {
-        print!(" !");
-        dump_glob(glob);
+        res += " !";
+        res += &format_glob(glob);
     });
-    println!();
+    res
+}
+
+fn dump_path_condition(path_condition: &GlobCondition) {
+    println!("    {}", format_path_condition(path_condition));
     path_condition
         .content_conditions
         .iter()
-        .for_each(dump_content_condition);
+        .for_each(|content_condition_node| {
+            println!(
+                "        {}",
+                format_content_condition(content_condition_node)
+            );
+            if let ContentCondition::Match(regex_condition) = &content_condition_node.condition {
+                if let Some(count_condition) = &regex_condition.count {
+                    println!("        {}", format_match_count_condition(count_condition));
+                }
+                if let Some(replacement) = &regex_condition.replacement {
+                    println!(
+                        "        replace \"{}\"",
+                        replacement.replace('\\', "\\\\").replace('"', "\\\"")
+                    );
+                }
+            }
+        });
 }
 
 fn dump_tags(rule: &Rule) {
@@ -101,6 +138,9 @@ fn dump_tags(rule: &Rule) {
 
 fn dump_rule(rule: &Rule) {
     println!("[{}]", rule.title.replace(']', "]]"));
+    if let Some(id) = &rule.id {
+        println!("    id {id}");
+    }
     if !rule.tags.is_empty() {
         print!("    tags ");
         dump_tags(rule);
@@ -0,0 +1,239 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::config::Config;
+use crate::ruleset::{
+    ConditionLogic, ContentCondition, ContentConditionNode, GlobCondition, LineRange,
+    RegexCondition, Rule, SizeCondition, SizeOperator,
+};
+
+#[derive(serde::Serialize)]
+struct DumpedConfig<'a> {
+    roots: Vec<String>,
+    rules: Vec<DumpedRule<'a>>,
+}
+
+#[derive(serde::Serialize)]
+struct DumpedRule<'a> {
+    number: usize,
+    title: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<&'a str>,
+    tags: Vec<&'a str>,
+    is_reporting_target: bool,
+    path_conditions: Vec<DumpedPathCondition<'a>>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+enum DumpedLogic {
+    Positive,
+    Negative,
+}
+
+#[derive(serde::Serialize)]
+struct DumpedPathCondition<'a> {
+    number: usize,
+    logic: DumpedLogic,
+    patterns: Vec<&'a str>,
+    excludes: Vec<&'a str>,
+    is_reporting_target: bool,
+    has_reporting_target: bool,
+    content_conditions: Vec<DumpedContentCondition<'a>>,
+}
+
+#[derive(serde::Serialize)]
+struct DumpedContentCondition<'a> {
+    number: usize,
+    is_reporting_target: bool,
+    #[serde(flatten)]
+    condition: DumpedContentConditionKind<'a>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum DumpedContentConditionKind<'a> {
+    Match(DumpedRegexCondition<'a>),
+    NoMatch(DumpedRegexCondition<'a>),
+    Size(DumpedSizeCondition),
+    Lines(DumpedSizeCondition),
+}
+
+#[derive(serde::Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum DumpedLineRange {
+    First { count: u64 },
+    Last { count: u64 },
+    Span { first: u64, last: u64 },
+}
+
+#[derive(serde::Serialize)]
+struct DumpedRegexCondition<'a> {
+    patterns: Vec<&'a str>,
+    excludes: Vec<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line_range: Option<DumpedLineRange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    count: Option<DumpedSizeCondition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    follow_up: Option<Box<DumpedFollowUpCondition<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    replacement: Option<&'a str>,
+}
+
+#[derive(serde::Serialize)]
+struct DumpedFollowUpCondition<'a> {
+    #[serde(flatten)]
+    condition: DumpedRegexCondition<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_distance: Option<u64>,
+}
+
+#[derive(serde::Serialize)]
+struct DumpedSizeCondition {
+    operator: &'static str,
+    value: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    upper_value: Option<u64>,
+}
+
+fn convert_regex_condition(regex_condition: &RegexCondition) -> DumpedRegexCondition<'_> {
+    DumpedRegexCondition {
+        patterns: regex_condition
+            .patterns
+            .iter()
+            .map(|regex| regex.as_str())
+            .collect(),
+        excludes: regex_condition
+            .excludes
+            .iter()
+            .map(|regex| regex.as_str())
+            .collect(),
+        line_range: regex_condition
+            .line_range
+            .as_ref()
+            .map(|line_range| match *line_range {
+                LineRange::First(count) => DumpedLineRange::First { count },
+                LineRange::Last(count) => DumpedLineRange::Last { count },
+                LineRange::Span(first, last) => DumpedLineRange::Span { first, last },
+            }),
+        count: regex_condition.count.as_ref().map(convert_size_condition),
+        follow_up: regex_condition.follow_up.as_ref().map(|follow_up| {
+            Box::new(DumpedFollowUpCondition {
+                condition: convert_regex_condition(&follow_up.condition),
+                max_distance: follow_up.max_distance,
+            })
+        }),
+        replacement: regex_condition.replacement.as_deref(),
+    }
+}
+
+fn convert_size_condition(size_condition: &SizeCondition) -> DumpedSizeCondition {
+    DumpedSizeCondition {
+        operator: match size_condition.operator {
+            SizeOperator::GreaterEqual => ">=",
+            SizeOperator::Greater => ">",
+            SizeOperator::LessEqual => "<=",
+            SizeOperator::Less => "<",
+            SizeOperator::Equal => "==",
+            SizeOperator::NotEqual => "!=",
+            SizeOperator::Range(_) => "..",
+        },
+        value: size_condition.value,
+        upper_value: match size_condition.operator {
+            SizeOperator::Range(upper_value) => Some(upper_value),
+            _ => None,
+        },
+    }
+}
+
+fn convert_content_condition(
+    content_condition_node: &ContentConditionNode,
+) -> DumpedContentCondition<'_> {
+    DumpedContentCondition {
+        number: content_condition_node.number,
+        is_reporting_target: content_condition_node.is_reporting_target,
+        condition: match &content_condition_node.condition {
+            ContentCondition::Match(regex_condition) => {
+                DumpedContentConditionKind::Match(convert_regex_condition(regex_condition))
+            }
+            ContentCondition::NoMatch(regex_condition) => {
+                DumpedContentConditionKind::NoMatch(convert_regex_condition(regex_condition))
+            }
+            ContentCondition::Size(size_condition) => {
+                DumpedContentConditionKind::Size(convert_size_condition(size_condition))
+            }
+            ContentCondition::Lines(size_condition) => {
+                DumpedContentConditionKind::Lines(convert_size_condition(size_condition))
+            }
+        },
+    }
+}
+
+fn convert_path_condition(path_condition: &GlobCondition) -> DumpedPathCondition<'_> {
+    DumpedPathCondition {
+        number: path_condition.number,
+        logic: match path_condition.logic {
+            ConditionLogic::Positive => DumpedLogic::Positive,
+            ConditionLogic::Negative => DumpedLogic::Negative,
+        },
+        patterns: path_condition
+            .patterns
+            .iter()
+            .map(|glob| glob.as_str())
+            .collect(),
+        excludes: path_condition
+            .excludes
+            .iter()
+            .map(|glob| glob.as_str())
+            .collect(),
+        is_reporting_target: path_condition.is_reporting_target,
+        has_reporting_target: path_condition.has_reporting_target,
+        content_conditions: path_condition
+            .content_conditions
+            .iter()
+            .map(convert_content_condition)
+            .collect(),
+    }
+}
+
+fn convert_rule(rule: &Rule) -> DumpedRule<'_> {
+    let mut tags: Vec<_> = rule.tags.iter().map(|tag| tag.as_str()).collect();
+    tags.sort();
+    DumpedRule {
+        number: rule.number,
+        title: &rule.title,
+        id: rule.id.as_deref(),
+        tags,
+        is_reporting_target: rule.is_reporting_target,
+        path_conditions: rule
+            .path_conditions
+            .iter()
+            .map(convert_path_condition)
+            .collect(),
+    }
+}
+
+impl Config {
+    /// Dump config in JSON format
+    ///
+    /// Unlike text dump, this dumps the compiled ruleset, so it
+    /// includes rule and condition numbers and reporting targets
+    pub fn dump_json(self) {
+        let roots = self
+            .roots
+            .iter()
+            .map(|root| root.display().to_string())
+            .collect();
+        let ruleset = self.ruleset.compile();
+
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&DumpedConfig {
+                roots,
+                rules: ruleset.rules.iter().map(convert_rule).collect(),
+            })
+            .unwrap()
+        );
+    }
+}
@@ -10,11 +10,12 @@ mod tests;
 use crate::config::Config;
 use crate::ruleset::Rule as RulesetRule;
 use crate::ruleset::{
-    ConditionLogic, ContentCondition, ContentConditionNode, Glob, GlobCondition, Regex,
-    RegexCondition, SizeCondition, SizeOperator,
+    ConditionLogic, ContentCondition, ContentConditionNode, FollowUpCondition, Glob, GlobCondition,
+    LineRange, Regex, RegexCondition, SizeCondition, SizeOperator,
 };
-use anyhow::{Context, Error};
+use anyhow::Error;
 use pest::Parser;
+use pest::error::{InputLocation, LineColLocation};
 use std::borrow::Cow;
 use std::collections::{HashSet, VecDeque};
 use std::fs;
@@ -177,30 +178,203 @@ fn parse_regexes_condition(pair: pest::iterators::Pair<Rule>) -> Result<RegexCon
     Ok(cond)
 }
 
-fn parse_size_condition(pair: pest::iterators::Pair<Rule>) -> Result<SizeCondition, PestError> {
+fn parse_line_range(pair: pest::iterators::Pair<Rule>) -> Result<LineRange, PestError> {
+    let item = pair.into_inner().next().unwrap();
+    match item.as_rule() {
+        Rule::line_range_first => Ok(LineRange::First(parse_number(
+            &item.into_inner().next().unwrap(),
+        )?)),
+        Rule::line_range_last => Ok(LineRange::Last(parse_number(
+            &item.into_inner().next().unwrap(),
+        )?)),
+        Rule::line_range_span => {
+            let range = item.into_inner().next().unwrap();
+            let span = range.as_span();
+            let (first, last) = parse_range(range, parse_number)?;
+            if first == 0 {
+                return Err(PestError::new_from_span(
+                    pest::error::ErrorVariant::<Rule>::CustomError {
+                        message: "line numbers start from 1".to_owned(),
+                    },
+                    span,
+                ));
+            }
+            Ok(LineRange::Span(first, last))
+        }
+        _ => unreachable!(
+            "unexpected parser rule type in parse_line_range {:#?}",
+            item
+        ),
+    }
+}
+
+fn parse_follow_up(pair: pest::iterators::Pair<Rule>) -> Result<FollowUpCondition, PestError> {
     let mut iter = pair.into_inner();
-    let operator = iter.next().unwrap();
-    let value = iter.next().unwrap();
-
-    Ok(SizeCondition {
-        operator: match operator.as_str() {
-            ">=" => SizeOperator::GreaterEqual,
-            ">" => SizeOperator::Greater,
-            "<=" => SizeOperator::LessEqual,
-            "<" => SizeOperator::Less,
-            "=" => SizeOperator::Equal,
-            "==" => SizeOperator::Equal,
-            "!=" => SizeOperator::NotEqual,
-            "<>" => SizeOperator::NotEqual,
-            other => unreachable!("unexpected size operator {other}",),
-        },
-        value: value
-            .as_str()
-            .parse()
-            .map_err(|e| error_into_pest_error(e, &value))?,
+    Ok(FollowUpCondition {
+        condition: parse_regexes_condition(iter.next().unwrap())?,
+        max_distance: iter
+            .next()
+            .map(|item| parse_number(&item.into_inner().next().unwrap()))
+            .transpose()?,
     })
 }
 
+fn parse_replacement(pair: pest::iterators::Pair<Rule>) -> String {
+    let quoted = pair.into_inner().next().unwrap();
+    let text = quoted.as_str();
+    let text = &text[1..text.len() - 1];
+    if quoted.as_rule() == Rule::squoted_str {
+        return text.to_owned();
+    }
+
+    // only \\ and \" escapes are allowed by the grammar
+    let mut output = String::new();
+    let mut escaped = false;
+    for c in text.chars() {
+        if c == '\\' && !escaped {
+            escaped = true;
+        } else {
+            output.push(c);
+            escaped = false;
+        }
+    }
+    output
+}
+
+fn parse_regex_directive(pair: pest::iterators::Pair<Rule>) -> Result<RegexCondition, PestError> {
+    let mut iter = pair.into_inner();
+    let mut cond = parse_regexes_condition(iter.next().unwrap())?;
+    for item in iter {
+        match item.as_rule() {
+            Rule::line_range => cond.line_range = Some(parse_line_range(item)?),
+            Rule::follow_up => cond.follow_up = Some(Box::new(parse_follow_up(item)?)),
+            Rule::rule_directive_matches => {
+                cond.count = Some(parse_size_condition(
+                    item.into_inner().next().unwrap(),
+                    parse_number,
+                )?)
+            }
+            Rule::rule_directive_replace => {
+                cond.replacement = Some(parse_replacement(item.into_inner().next().unwrap()))
+            }
+            _ => unreachable!(
+                "unexpected parser rule type in parse_regex_directive {:#?}",
+                item
+            ),
+        }
+    }
+    Ok(cond)
+}
+
+fn parse_size_operator(s: &str) -> SizeOperator {
+    match s {
+        ">=" => SizeOperator::GreaterEqual,
+        ">" => SizeOperator::Greater,
+        "<=" => SizeOperator::LessEqual,
+        "<" => SizeOperator::Less,
+        "=" => SizeOperator::Equal,
+        "==" => SizeOperator::Equal,
+        "!=" => SizeOperator::NotEqual,
+        "<>" => SizeOperator::NotEqual,
+        other => unreachable!("unexpected size operator {other}",),
+    }
+}
+
+fn parse_number(pair: &pest::iterators::Pair<Rule>) -> Result<u64, PestError> {
+    pair.as_str()
+        .parse()
+        .map_err(|e| error_into_pest_error(e, pair))
+}
+
+fn parse_size(pair: &pest::iterators::Pair<Rule>) -> Result<u64, PestError> {
+    let s = pair.as_str();
+    let unit_start = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+
+    let multiplier: u64 = match s[unit_start..].to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kib" => 1 << 10,
+        "m" | "mib" => 1 << 20,
+        "g" | "gib" => 1 << 30,
+        "t" | "tib" => 1 << 40,
+        "kb" => 1_000,
+        "mb" => 1_000_000,
+        "gb" => 1_000_000_000,
+        "tb" => 1_000_000_000_000,
+        other => unreachable!("unexpected size unit {other}"),
+    };
+
+    let custom_error = |message: &str| {
+        PestError::new_from_span(
+            pest::error::ErrorVariant::<Rule>::CustomError {
+                message: message.to_owned(),
+            },
+            pair.as_span(),
+        )
+    };
+
+    if let Ok(value) = s[..unit_start].parse::<u64>() {
+        value
+            .checked_mul(multiplier)
+            .ok_or_else(|| custom_error("size is too large"))
+    } else if multiplier == 1 {
+        Err(custom_error("fractional sizes are only allowed with units"))
+    } else {
+        let value: f64 = s[..unit_start]
+            .parse()
+            .map_err(|e| error_into_pest_error(e, pair))?;
+        Ok((value * multiplier as f64).round() as u64)
+    }
+}
+
+fn parse_range(
+    pair: pest::iterators::Pair<Rule>,
+    parse_value: fn(&pest::iterators::Pair<Rule>) -> Result<u64, PestError>,
+) -> Result<(u64, u64), PestError> {
+    let span = pair.as_span();
+    let mut iter = pair.into_inner();
+    let lower_value = parse_value(&iter.next().unwrap())?;
+    let upper_value = parse_value(&iter.next().unwrap())?;
+
+    if lower_value > upper_value {
+        return Err(PestError::new_from_span(
+            pest::error::ErrorVariant::<Rule>::CustomError {
+                message: "lower bound of the range is greater than upper bound".to_owned(),
+            },
+            span,
+        ));
+    }
+
+    Ok((lower_value, upper_value))
+}
+
+fn parse_size_condition(
+    pair: pest::iterators::Pair<Rule>,
+    parse_value: fn(&pest::iterators::Pair<Rule>) -> Result<u64, PestError>,
+) -> Result<SizeCondition, PestError> {
+    let mut iter = pair.into_inner();
+    let first = iter.next().unwrap();
+
+    match first.as_rule() {
+        Rule::number_range | Rule::size_range => {
+            let (lower_value, upper_value) = parse_range(first, parse_value)?;
+            Ok(SizeCondition {
+                operator: SizeOperator::Range(upper_value),
+                value: lower_value,
+            })
+        }
+        Rule::range_operation => Ok(SizeCondition {
+            operator: parse_size_operator(first.as_str()),
+            value: parse_value(&iter.next().unwrap())?,
+        }),
+        _ => unreachable!(
+            "unexpected parser rule type in parse_size_condition {:#?}",
+            first
+        ),
+    }
+}
+
 fn parse_files_condition(pair: pest::iterators::Pair<Rule>) -> Result<GlobCondition, PestError> {
     let mut condition: GlobCondition = Default::default();
 
@@ -214,22 +388,19 @@ fn parse_files_condition(pair: pest::iterators::Pair<Rule>) -> Result<GlobCondit
             }
             Rule::rule_directive_match => {
                 condition.content_conditions.push(ContentConditionNode::new(
-                    ContentCondition::Match(parse_regexes_condition(
-                        item.into_inner().next().unwrap(),
-                    )?),
+                    ContentCondition::Match(parse_regex_directive(item)?),
                 ));
             }
             Rule::rule_directive_nomatch => {
                 condition.content_conditions.push(ContentConditionNode::new(
-                    ContentCondition::NoMatch(parse_regexes_condition(
-                        item.into_inner().next().unwrap(),
-                    )?),
+                    ContentCondition::NoMatch(parse_regex_directive(item)?),
                 ));
             }
             Rule::rule_directive_size => {
                 condition.content_conditions.push(ContentConditionNode::new(
                     ContentCondition::Size(parse_size_condition(
                         item.into_inner().next().unwrap(),
+                        parse_size,
                     )?),
                 ));
             }
@@ -237,6 +408,7 @@ fn parse_files_condition(pair: pest::iterators::Pair<Rule>) -> Result<GlobCondit
                 condition.content_conditions.push(ContentConditionNode::new(
                     ContentCondition::Lines(parse_size_condition(
                         item.into_inner().next().unwrap(),
+                        parse_number,
                     )?),
                 ));
             }
@@ -254,6 +426,7 @@ fn parse_rule(
     pair: pest::iterators::Pair<Rule>,
     rule_number: usize,
     config_path: &Path,
+    line_offset: usize,
     template_rule: &mut RulesetRule,
 ) -> Result<Option<RulesetRule>, PestError> {
     let mut rule: RulesetRule = Default::default();
@@ -261,7 +434,7 @@ fn parse_rule(
     for item in pair.into_inner() {
         match item.as_rule() {
             Rule::rule_title => {
-                let line_number = item.as_span().start_pos().line_col().0;
+                let line_number = item.as_span().start_pos().line_col().0 + line_offset;
                 let title = item.into_inner().next().unwrap().as_str();
                 if title.is_empty() {
                     rule.title = format!(
@@ -274,6 +447,9 @@ fn parse_rule(
                     rule.title = parse_title(title);
                 }
             }
+            Rule::rule_directive_id => {
+                rule.id = Some(item.into_inner().next().unwrap().as_str().to_lowercase())
+            }
             Rule::rule_directive_tags => rule.tags = parse_tags(item.into_inner().next().unwrap()),
             Rule::rule_directive_files => {
                 rule.path_conditions.push(parse_files_condition(item)?);
@@ -361,87 +537,214 @@ fn parse_config_directive_glob(
     Ok(res)
 }
 
-fn parse_file(config_text: &str, config_path: &Path) -> Result<Config, PestError> {
-    let mut config: Config = Default::default();
+fn rename_parser_rule(parser_rule: &Rule) -> String {
+    match *parser_rule {
+        Rule::EOI => "end of file".to_owned(),
+        Rule::config_directive_root => "\"root\" directive".to_owned(),
+        Rule::excluded_glob => "exclusion glob pattern prefixed with exclamation mark".to_owned(),
+        Rule::excluded_regexp => {
+            "exclusion regexp pattern prefixed with exclamation mark".to_owned()
+        }
+        Rule::included_glob => "glob pattern".to_owned(),
+        Rule::included_regexp => "regexp pattern".to_owned(),
+        Rule::line_range => "line range".to_owned(),
+        Rule::follow_up => "\"then\" patterns".to_owned(),
+        Rule::rule_directive_files_inner => "\"files\" condition".to_owned(),
+        Rule::rule_directive_match => "\"match\" condition".to_owned(),
+        Rule::rule_directive_matches => "\"matches\" condition".to_owned(),
+        Rule::rule_directive_replace => "\"replace\" directive".to_owned(),
+        Rule::rule_directive_nofiles => "\"nofiles\" condition".to_owned(),
+        Rule::rule_directive_nomatch => "\"nomatch\" condition".to_owned(),
+        Rule::rule_directive_id => "\"id\" directive".to_owned(),
+        Rule::rule_directive_tags => "\"tags\" directive".to_owned(),
+        Rule::rule_title_outer => "rule title in brackets".to_owned(),
+        Rule::simple_glob => "glob pattern".to_owned(),
+        // XXX: how to make pest always descend into main rule?
+        Rule::file => "omnilinter configuration file".to_owned(),
+        Rule::rule_section => "rule".to_owned(),
+        other => format!("{other:?}"),
+    }
+}
 
-    let file = ConfigParser::parse(Rule::file, config_text)
-        .map_err(|err| {
-            err.renamed_rules(|parser_rule| match *parser_rule {
-                Rule::EOI => "end of file".to_owned(),
-                Rule::config_directive_root => "\"root\" directive".to_owned(),
-                Rule::excluded_glob => {
-                    "exclusion glob pattern prefixed with exclamation mark".to_owned()
-                }
-                Rule::excluded_regexp => {
-                    "exclusion regexp pattern prefixed with exclamation mark".to_owned()
-                }
-                Rule::included_glob => "glob pattern".to_owned(),
-                Rule::included_regexp => "regexp pattern".to_owned(),
-                Rule::rule_directive_files_inner => "\"files\" condition".to_owned(),
-                Rule::rule_directive_match => "\"match\" condition".to_owned(),
-                Rule::rule_directive_nofiles => "\"nofiles\" condition".to_owned(),
-                Rule::rule_directive_nomatch => "\"nomatch\" condition".to_owned(),
-                Rule::rule_directive_tags => "\"tags\" directive".to_owned(),
-                Rule::rule_title_outer => "rule title in brackets".to_owned(),
-                Rule::simple_glob => "glob pattern".to_owned(),
-                // XXX: how to make pest always descend into main rule?
-                Rule::file => "omnilinter configuration file".to_owned(),
-                other => format!("{other:?}"),
-            })
-        })?
-        .next()
-        .unwrap();
+/// Split config text into independently parseable sections
+///
+/// The first section contains everything before the first rule (e.g.
+/// config directives), and each following section contains a single
+/// rule, starting with a line which begins with the rule title.
+fn split_into_sections(config_text: &str) -> Vec<&str> {
+    let mut sections = vec![];
+    let mut section_start = 0;
+    let mut line_start = 0;
+
+    for line in config_text.split_inclusive('\n') {
+        if line.trim_start_matches([' ', '\t']).starts_with('[') {
+            sections.push(&config_text[section_start..line_start]);
+            section_start = line_start;
+        }
+        line_start += line.len();
+    }
+    sections.push(&config_text[section_start..]);
+
+    sections
+}
+
+/// Check whether the section defines a template rule
+fn is_template_section(section: &str) -> bool {
+    section
+        .trim_start_matches([' ', '\t'])
+        .strip_prefix(&format!("[{TEMPLATE_RULE_TITLE}]"))
+        .is_some_and(|rest| !rest.starts_with(']')) // escaped bracket continues the title
+}
+
+/// Convert error position in the section into position in the whole config
+fn offset_error(mut err: PestError, line_offset: usize, byte_offset: usize) -> PestError {
+    err.line_col = match err.line_col {
+        LineColLocation::Pos((line, col)) => LineColLocation::Pos((line + line_offset, col)),
+        LineColLocation::Span((start_line, start_col), (end_line, end_col)) => {
+            LineColLocation::Span(
+                (start_line + line_offset, start_col),
+                (end_line + line_offset, end_col),
+            )
+        }
+    };
+    err.location = match err.location {
+        InputLocation::Pos(pos) => InputLocation::Pos(pos + byte_offset),
+        InputLocation::Span((start, end)) => {
+            InputLocation::Span((start + byte_offset, end + byte_offset))
+        }
+    };
+    err
+}
+
+/// Error reported when template section fails to parse
+fn template_error(section: &str) -> PestError {
+    let title_start = section.len() - section.trim_start_matches([' ', '\t']).len();
+    PestError::new_from_pos(
+        pest::error::ErrorVariant::<Rule>::CustomError {
+            message: "template is not applied to following rules because of errors".to_owned(),
+        },
+        pest::Position::new(section, title_start).unwrap(),
+    )
+}
+
+fn parse_file(config_text: &str, config_path: &Path) -> (Config, Vec<PestError>) {
+    let mut config: Config = Default::default();
+    let mut errors: Vec<PestError> = Default::default();
 
     let mut template_rule: RulesetRule = Default::default();
 
-    for item in file.into_inner() {
-        match item.as_rule() {
-            Rule::config_directive_root => {
-                config.roots.append(&mut parse_config_directive_glob(
-                    item.into_inner().next().unwrap(),
-                    config_path,
-                )?);
-            }
-            Rule::config_directive_include => {
-                config.includes.append(&mut parse_config_directive_glob(
-                    item.into_inner().next().unwrap(),
-                    config_path,
-                )?);
-            }
-            Rule::rule => {
-                if let Some(rule) = parse_rule(
-                    item,
-                    config.ruleset.rules.len(),
-                    config_path,
-                    &mut template_rule,
-                )? {
-                    config.ruleset.rules.push(rule);
+    let mut line_offset = 0;
+    let mut byte_offset = 0;
+
+    for (section_number, section) in split_into_sections(config_text).into_iter().enumerate() {
+        let parser_rule = if section_number == 0 {
+            Rule::file
+        } else {
+            Rule::rule_section
+        };
+
+        // sections are parsed separately, so positions are adjusted
+        // to match the ones in the whole config
+        let section_line_offset = line_offset;
+        let section_byte_offset = byte_offset;
+        line_offset += section.matches('\n').count();
+        byte_offset += section.len();
+
+        let mut section_errors: Vec<PestError> = vec![];
+
+        match ConfigParser::parse(parser_rule, section) {
+            Ok(mut pairs) => {
+                for item in pairs.next().unwrap().into_inner() {
+                    let res = match item.as_rule() {
+                        Rule::config_directive_root => parse_config_directive_glob(
+                            item.into_inner().next().unwrap(),
+                            config_path,
+                        )
+                        .map(|mut roots| config.roots.append(&mut roots)),
+                        Rule::config_directive_include => parse_config_directive_glob(
+                            item.into_inner().next().unwrap(),
+                            config_path,
+                        )
+                        .map(|mut includes| config.includes.append(&mut includes)),
+                        Rule::rule => parse_rule(
+                            item,
+                            config.ruleset.rules.len(),
+                            config_path,
+                            section_line_offset,
+                            &mut template_rule,
+                        )
+                        .map(|rule| {
+                            if let Some(rule) = rule {
+                                config.ruleset.rules.push(rule);
+                            }
+                        }),
+                        Rule::EOI => Ok(()),
+                        _ => unreachable!("unexpected parser rule type in parse_file {:#?}", item),
+                    };
+                    if let Err(err) = res {
+                        section_errors.push(err);
+                    }
                 }
             }
-            Rule::EOI => (),
-            _ => unreachable!("unexpected parser rule type in from_str {:#?}", item),
+            Err(err) => section_errors.push(err.renamed_rules(rename_parser_rule)),
+        }
+
+        if !section_errors.is_empty() && is_template_section(section) {
+            // do not silently keep applying previous template
+            template_rule = Default::default();
+            section_errors.push(template_error(section));
         }
+
+        errors.extend(
+            section_errors
+                .into_iter()
+                .map(|err| offset_error(err, section_line_offset, section_byte_offset)),
+        );
     }
 
-    Ok(config)
+    (config, errors)
 }
 
 impl Config {
     #[cfg(test)]
     pub fn from_str(s: &str) -> Result<Config, Error> {
-        Ok(parse_file(s, Path::new("???"))?)
+        let (config, errors) = parse_file(s, Path::new("???"));
+        if let Some(err) = errors.into_iter().next() {
+            return Err(err.into());
+        }
+        Ok(config)
     }
 
-    pub fn from_file(path: &Path) -> Result<Config, Error> {
-        let content = fs::read_to_string(path)
-            .with_context(|| format!("failed to read config file {}", path.display()))?;
+    fn from_file_collect_errors(path: &Path, errors: &mut Vec<Error>) -> Config {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) => {
+                errors.push(
+                    Error::from(err)
+                        .context(format!("failed to read config file {}", path.display())),
+                );
+                return Config::new();
+            }
+        };
+
+        let (config, parse_errors) = parse_file(&content, path);
+
+        errors.extend(parse_errors.into_iter().map(|err| {
+            Error::from(err.with_path(&path.display().to_string()))
+                .context(format!("failed to parse config file {}", path.display()))
+        }));
 
-        parse_file(&content, path)
-            .map_err(|e| e.with_path(&path.display().to_string()))
-            .with_context(|| format!("failed to parse config file {}", path.display()))
+        config
     }
 
-    pub fn from_file_expand_includes(path: &Path) -> Result<Config, Error> {
+    /// Parse config file along with all included configs, collecting errors
+    ///
+    /// Parsing does not stop on errors, so all of these are reported at once,
+    /// and returned config contains all rules which were parsed successfully.
+    pub fn from_file_expand_includes_collect_errors(
+        path: &Path,
+        errors: &mut Vec<Error>,
+    ) -> Config {
         let mut config = Config::new();
         let mut queue = VecDeque::new();
         let mut seen_paths = HashSet::new();
@@ -453,13 +756,22 @@ impl Config {
                 continue;
             }
 
-            config.merge_from(Self::from_file(&current_path)?);
+            config.merge_from(Self::from_file_collect_errors(&current_path, errors));
             config
                 .includes
                 .drain(0..)
                 .for_each(|include| queue.push_back(include));
         }
 
+        config
+    }
+
+    pub fn from_file_expand_includes(path: &Path) -> Result<Config, Error> {
+        let mut errors = vec![];
+        let config = Self::from_file_expand_includes_collect_errors(path, &mut errors);
+        if let Some(err) = errors.into_iter().next() {
+            return Err(err);
+        }
         Ok(config)
     }
 }
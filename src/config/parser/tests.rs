@@ -1,8 +1,11 @@
 // SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use super::parse_file;
 use crate::config::Config;
-use crate::ruleset::{ContentCondition, Regex, SizeCondition, SizeOperator};
+use crate::ruleset::{
+    ContentCondition, FollowUpCondition, LineRange, Regex, SizeCondition, SizeOperator,
+};
 use testutils::lines;
 
 fn get_first_regex_pattern(config: &Config) -> &Regex {
@@ -21,6 +24,14 @@ fn get_first_regex_exclude(config: &Config) -> &Regex {
     }
 }
 
+fn get_first_line_range(config: &Config) -> Option<&LineRange> {
+    match &config.ruleset.rules[0].path_conditions[0].content_conditions[0].condition {
+        ContentCondition::Match(regex_condition) => regex_condition.line_range.as_ref(),
+        ContentCondition::NoMatch(regex_condition) => regex_condition.line_range.as_ref(),
+        _ => panic!(),
+    }
+}
+
 fn get_first_size_condition(config: &Config) -> &SizeCondition {
     match &config.ruleset.rules[0].path_conditions[0].content_conditions[0].condition {
         ContentCondition::Size(size_condition) => size_condition,
@@ -81,6 +92,24 @@ mod parse_tags {
     }
 }
 
+mod parse_id {
+    use super::*;
+
+    #[test]
+    fn id() {
+        let text = lines!["[]", "id Rule-1", "tags a"];
+        let config = Config::from_str(text).unwrap();
+        assert_eq!(config.ruleset.rules[0].id.as_deref(), Some("rule-1"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_id() {
+        let text = lines!["[]", "id rule[1]"];
+        Config::from_str(text).unwrap();
+    }
+}
+
 mod parse_regexp {
     use super::*;
 
@@ -158,6 +187,176 @@ mod parse_regexp {
     }
 }
 
+mod parse_line_range {
+    use super::*;
+
+    #[test]
+    fn none() {
+        let text = lines!["[]", "files *", "match /a/"];
+        let config = Config::from_str(text).unwrap();
+        assert_eq!(get_first_line_range(&config), None);
+    }
+
+    #[test]
+    fn first() {
+        let text = lines!["[]", "files *", "match /a/ within first 5 lines"];
+        let config = Config::from_str(text).unwrap();
+        assert_eq!(get_first_line_range(&config), Some(&LineRange::First(5)));
+    }
+
+    #[test]
+    fn last() {
+        let text = lines!["[]", "files *", "nomatch /a/ !/b/ within last 1 line"];
+        let config = Config::from_str(text).unwrap();
+        assert_eq!(get_first_line_range(&config), Some(&LineRange::Last(1)));
+    }
+
+    #[test]
+    fn span() {
+        let text = lines!["[]", "files *", "match /a/ within lines 2..5"];
+        let config = Config::from_str(text).unwrap();
+        assert_eq!(get_first_line_range(&config), Some(&LineRange::Span(2, 5)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn span_from_zero() {
+        let text = lines!["[]", "files *", "match /a/ within lines 0..5"];
+        Config::from_str(text).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn without_qualifier() {
+        let text = lines!["[]", "files *", "match /a/ within 5 lines"];
+        Config::from_str(text).unwrap();
+    }
+}
+
+mod parse_match_count {
+    use super::*;
+
+    fn get_first_match_count(config: &Config) -> Option<&SizeCondition> {
+        match &config.ruleset.rules[0].path_conditions[0].content_conditions[0].condition {
+            ContentCondition::Match(regex_condition) => regex_condition.count.as_ref(),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn basic() {
+        let text = lines!["[]", "files *", "match /a/", "matches > 3"];
+        let config = Config::from_str(text).unwrap();
+        let count = get_first_match_count(&config).unwrap();
+        assert_eq!(count.operator, SizeOperator::Greater);
+        assert_eq!(count.value, 3);
+    }
+
+    #[test]
+    fn with_line_range() {
+        let text = lines![
+            "[]",
+            "files *",
+            "match /a/ within first 5 lines",
+            "matches 1..2"
+        ];
+        let config = Config::from_str(text).unwrap();
+        assert_eq!(
+            get_first_match_count(&config).unwrap().operator,
+            SizeOperator::Range(2)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn after_nomatch() {
+        let text = lines!["[]", "files *", "nomatch /a/", "matches > 3"];
+        Config::from_str(text).unwrap();
+    }
+}
+
+mod parse_follow_up {
+    use super::*;
+
+    fn get_first_follow_up(config: &Config) -> Option<&FollowUpCondition> {
+        match &config.ruleset.rules[0].path_conditions[0].content_conditions[0].condition {
+            ContentCondition::Match(regex_condition) => regex_condition.follow_up.as_deref(),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn basic() {
+        let text = lines!["[]", "files *", "match /a/ then /b/ !/c/"];
+        let config = Config::from_str(text).unwrap();
+        let follow_up = get_first_follow_up(&config).unwrap();
+        assert_eq!(follow_up.condition.patterns[0].as_str(), "b");
+        assert_eq!(follow_up.condition.excludes[0].as_str(), "c");
+        assert_eq!(follow_up.max_distance, None);
+    }
+
+    #[test]
+    fn with_distance() {
+        let text = lines!["[]", "files *", "match /a/ then /b/ within 10 lines"];
+        let config = Config::from_str(text).unwrap();
+        assert_eq!(get_first_follow_up(&config).unwrap().max_distance, Some(10));
+    }
+
+    #[test]
+    fn with_line_range() {
+        let text = lines!["[]", "files *", "match /a/ within last 5 lines then /b/"];
+        let config = Config::from_str(text).unwrap();
+        assert!(get_first_follow_up(&config).is_some());
+        assert_eq!(get_first_line_range(&config), Some(&LineRange::Last(5)));
+    }
+}
+
+mod parse_replacement {
+    use super::*;
+
+    fn get_first_replacement(config: &Config) -> Option<&str> {
+        match &config.ruleset.rules[0].path_conditions[0].content_conditions[0].condition {
+            ContentCondition::Match(regex_condition) => regex_condition.replacement.as_deref(),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn dquoted() {
+        let text = lines!["[]", "files *", "match /a(.)/", r#"replace "b$1 \"\\""#];
+        let config = Config::from_str(text).unwrap();
+        assert_eq!(get_first_replacement(&config), Some(r#"b$1 "\"#));
+    }
+
+    #[test]
+    fn squoted() {
+        let text = lines!["[]", "files *", "match /a/", r#"replace 'b\"'"#];
+        let config = Config::from_str(text).unwrap();
+        assert_eq!(get_first_replacement(&config), Some(r#"b\""#));
+    }
+
+    #[test]
+    fn after_match_count() {
+        let text = lines!["[]", "files *", "match /a/", "matches > 1", "replace ''"];
+        let config = Config::from_str(text).unwrap();
+        assert_eq!(get_first_replacement(&config), Some(""));
+    }
+
+    #[test]
+    #[should_panic]
+    fn unquoted() {
+        let text = lines!["[]", "files *", "match /a/", "replace b"];
+        Config::from_str(text).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn after_nomatch() {
+        let text = lines!["[]", "files *", "nomatch /a/", "replace 'b'"];
+        Config::from_str(text).unwrap();
+    }
+}
+
 mod parse_size_condition {
     use super::*;
 
@@ -182,6 +381,66 @@ mod parse_size_condition {
         );
         assert_eq!(get_first_size_condition(&config).value, 123);
     }
+
+    #[test]
+    fn units() {
+        for (text, value) in [
+            ("size >= 10B", 10),
+            ("size >= 10K", 10240),
+            ("size >= 10k", 10240),
+            ("size >= 10KiB", 10240),
+            ("size >= 10KB", 10000),
+            ("size >= 1.5MiB", 1572864),
+            ("size >= 2M", 2097152),
+            ("size >= 1G", 1073741824),
+            ("size >= 1TB", 1000000000000),
+        ] {
+            let config = Config::from_str(&format!("[]\nfiles *\n{text}\n")).unwrap();
+            assert_eq!(get_first_size_condition(&config).value, value, "{text}");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn fractional_without_unit() {
+        let text = lines!["[]", "files *", "size >= 1.5"];
+        Config::from_str(text).unwrap();
+    }
+
+    #[test]
+    fn range() {
+        let text = lines!["[]", "files *", "size 1K..2K"];
+        let config = Config::from_str(text).unwrap();
+        assert_eq!(
+            get_first_size_condition(&config).operator,
+            SizeOperator::Range(2048)
+        );
+        assert_eq!(get_first_size_condition(&config).value, 1024);
+    }
+
+    #[test]
+    fn range_with_spaces() {
+        let text = lines!["[]", "files *", "size 10 .. 20"];
+        let config = Config::from_str(text).unwrap();
+        assert_eq!(
+            get_first_size_condition(&config).operator,
+            SizeOperator::Range(20)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn reversed_range() {
+        let text = lines!["[]", "files *", "size 20..10"];
+        Config::from_str(text).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn units_in_lines() {
+        let text = lines!["[]", "files *", "lines >= 10K"];
+        Config::from_str(text).unwrap();
+    }
 }
 
 mod parse_globs {
@@ -365,3 +624,93 @@ mod template {
         assert_eq!(config.ruleset.rules[2].path_conditions.len(), 0);
     }
 }
+
+mod error_recovery {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn all_errors_reported() {
+        let text = lines![
+            "root ***",
+            "[first]",
+            "files ***",
+            "[second]",
+            "garbage",
+            "[third]",
+            "files *",
+            "match /[/"
+        ];
+        let (_, errors) = parse_file(text, Path::new("???"));
+        let error_lines: Vec<_> = errors
+            .iter()
+            .map(|err| match err.line_col {
+                pest::error::LineColLocation::Pos((line, _)) => line,
+                pest::error::LineColLocation::Span((line, _), _) => line,
+            })
+            .collect();
+        assert_eq!(error_lines, vec![1, 3, 5, 8]);
+    }
+
+    #[test]
+    fn valid_rules_preserved() {
+        let text = lines![
+            "[first]", "files *", "[second]", "garbage", "[third]", "files *"
+        ];
+        let (config, errors) = parse_file(text, Path::new("???"));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(config.ruleset.rules.len(), 2);
+        assert_eq!(config.ruleset.rules[0].title, "first");
+        assert_eq!(config.ruleset.rules[1].title, "third");
+    }
+
+    #[test]
+    fn indented_rules() {
+        let text = lines!["  [first]", "  garbage", "  [second]", "  files *"];
+        let (config, errors) = parse_file(text, Path::new("???"));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(config.ruleset.rules.len(), 1);
+        assert_eq!(config.ruleset.rules[0].title, "second");
+    }
+
+    #[test]
+    fn error_positions_in_later_sections() {
+        let text = lines![
+            "[first]",
+            "files *",
+            "",
+            "[second]",
+            "  files *",
+            "  garbage"
+        ];
+        let (_, errors) = parse_file(text, Path::new("???"));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].line_col,
+            pest::error::LineColLocation::Pos((6, 3))
+        );
+        assert_eq!(
+            errors[0].location,
+            pest::error::InputLocation::Pos(text.find("garbage").unwrap())
+        );
+    }
+
+    #[test]
+    fn failed_template_not_applied() {
+        let text = lines![
+            "[!template]",
+            "files *.py",
+            "[!template]",
+            "files *",
+            "match /[/",
+            "[first]",
+            "[second]"
+        ];
+        let (config, errors) = parse_file(text, Path::new("???"));
+        assert_eq!(errors.len(), 2);
+        assert!(errors[1].to_string().contains("template is not applied"));
+        assert_eq!(config.ruleset.rules.len(), 2);
+        assert!(config.ruleset.rules[0].path_conditions.is_empty());
+        assert!(config.ruleset.rules[1].path_conditions.is_empty());
+    }
+}
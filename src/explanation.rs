@@ -0,0 +1,90 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::ruleset::Rule;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Outcome of a single content condition check on a specific file
+#[derive(Default, Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "coverage"), derive(Debug))]
+pub enum ContentConditionOutcome {
+    /// Condition was not evaluated, for instance because
+    /// another condition has already failed for this file
+    #[default]
+    NotChecked,
+
+    /// Pattern has matched on given lines
+    Matched(Vec<u64>),
+
+    /// Pattern has not matched anywhere in the file
+    NotMatched,
+
+    /// Exact size or number of lines
+    Value(u64),
+
+    /// File was not read completely, but it has at least given number of lines
+    AtLeast(u64),
+}
+
+#[cfg_attr(not(feature = "coverage"), derive(Debug))]
+pub struct FileTrace {
+    pub path: Rc<PathBuf>,
+    /// Outcomes of content conditions, in order of their definition
+    pub outcomes: Vec<ContentConditionOutcome>,
+    pub is_matched: bool,
+}
+
+#[derive(Default)]
+#[cfg_attr(not(feature = "coverage"), derive(Debug))]
+pub struct PathConditionTrace {
+    /// Number of paths matched by the condition
+    pub matched_paths_count: usize,
+    /// First path matched; for `nofiles` condition this is the offending path
+    pub first_matched_path: Option<Rc<PathBuf>>,
+    /// Content checks performed on matched paths
+    pub files: Vec<FileTrace>,
+}
+
+/// Record of how a rule was evaluated on a root, for --explain
+#[cfg_attr(not(feature = "coverage"), derive(Debug))]
+pub struct Explanation<'a> {
+    pub rule: &'a Rule,
+    pub root: &'a Path,
+    /// Traces of path conditions, in order of their definition
+    pub path_conditions: Vec<PathConditionTrace>,
+    pub is_matched: bool,
+}
+
+impl<'a> Explanation<'a> {
+    pub fn new(rule: &'a Rule, root: &'a Path) -> Self {
+        Self {
+            rule,
+            root,
+            path_conditions: rule
+                .path_conditions
+                .iter()
+                .map(|_| Default::default())
+                .collect(),
+            is_matched: false,
+        }
+    }
+
+    pub fn get_path_condition_trace(&mut self, condition_number: usize) -> &mut PathConditionTrace {
+        let index = self
+            .rule
+            .path_conditions
+            .iter()
+            .position(|condition| condition.number == condition_number)
+            .expect("path condition should belong to the explained rule");
+        &mut self.path_conditions[index]
+    }
+
+    pub fn add_matched_path(&mut self, condition_number: usize, path: &Rc<PathBuf>) {
+        let trace = self.get_path_condition_trace(condition_number);
+        trace.matched_paths_count += 1;
+        if trace.first_matched_path.is_none() {
+            trace.first_matched_path = Some(path.clone());
+        }
+    }
+}
@@ -0,0 +1,20 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::r#match::Match;
+
+/// 64-bit FNV-1a, used instead of std hasher because
+/// fingerprints need to be stable across builds
+pub fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Get fingerprint of the matched line content
+///
+/// It does not depend on line number and surrounding whitespace,
+/// so it stays the same when lines are moved around or reindented
+pub fn get_line_fingerprint(m: &Match) -> Option<u64> {
+    m.file.as_ref()?.span.as_ref().map(|span| span.fingerprint)
+}
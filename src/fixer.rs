@@ -0,0 +1,210 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+mod diff;
+
+use crate::r#match::MatchResult;
+use crate::ruleset::{ContentCondition, RegexCondition, Rule};
+use diff::format_unified_diff;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// Fix to apply to a single line of a file
+struct LineFix<'a> {
+    match_index: usize,
+    line_number: u64,
+    rule: &'a Rule,
+    condition: &'a RegexCondition,
+}
+
+/// Get condition which produces line matches for the rule, if it has a replacement
+fn get_fixing_condition(rule: &Rule) -> Option<&RegexCondition> {
+    rule.path_conditions
+        .iter()
+        .filter(|path_condition| path_condition.has_reporting_target)
+        .flat_map(|path_condition| path_condition.content_conditions.iter())
+        .filter(|content_condition_node| content_condition_node.is_reporting_target)
+        .find_map(
+            |content_condition_node| match &content_condition_node.condition {
+                ContentCondition::Match(regex_condition)
+                    if regex_condition.replacement.is_some() =>
+                {
+                    Some(regex_condition)
+                }
+                _ => None,
+            },
+        )
+}
+
+/// Apply replacement of the condition to a line, using the first pattern which matches it
+///
+/// For conditions with follow-up patterns, these are used, as the reported
+/// line is the one which completes the sequence
+fn fix_line(line: &str, condition: &RegexCondition) -> Option<String> {
+    let replacement = condition.replacement.as_ref()?;
+    let regex = condition
+        .get_reported_line_condition()
+        .patterns
+        .iter()
+        .find(|regex| regex.is_match(line))?;
+    let fixed = regex.replace_all(line, replacement);
+    (fixed != line).then(|| fixed.into_owned())
+}
+
+fn split_line_ending(line: &str) -> (&str, &str) {
+    let content = line
+        .strip_suffix("\r\n")
+        .or_else(|| line.strip_suffix('\n'))
+        .unwrap_or(line);
+    line.split_at(content.len())
+}
+
+/// Write file contents through a temporary file, so the file is never left half-written
+fn write_file_atomically(path: &Path, text: &str) -> Result<(), std::io::Error> {
+    let mut temp_name = std::ffi::OsString::from(".");
+    temp_name.push(path.file_name().unwrap_or_default());
+    temp_name.push(".omnilinter-fix");
+    let temp_path = path.with_file_name(temp_name);
+
+    let permissions = std::fs::metadata(path)?.permissions();
+    std::fs::write(&temp_path, text)
+        .and_then(|_| std::fs::set_permissions(&temp_path, permissions))
+        .and_then(|_| std::fs::rename(&temp_path, path))
+        .inspect_err(|_| {
+            let _ = std::fs::remove_file(&temp_path);
+        })
+}
+
+/// File lines before and after fixing, with line endings kept
+struct FixedFile {
+    original_lines: Vec<String>,
+    lines: Vec<String>,
+    fixed_match_indexes: Vec<usize>,
+    /// Indexes of fixes for which replacement did not change anything
+    unapplied_fix_indexes: Vec<usize>,
+}
+
+fn fix_file_lines(path: &Path, fixes: &[LineFix]) -> Result<FixedFile, std::io::Error> {
+    let text = std::fs::read_to_string(path)?;
+
+    let original_lines: Vec<String> = text.split_inclusive('\n').map(str::to_owned).collect();
+    let mut lines = original_lines.clone();
+    let mut fixed_match_indexes = vec![];
+    let mut unapplied_fix_indexes = vec![];
+
+    for (fix_index, fix) in fixes.iter().enumerate() {
+        let Some(line) = lines.get_mut(fix.line_number as usize) else {
+            unapplied_fix_indexes.push(fix_index);
+            continue;
+        };
+        let (content, line_ending) = split_line_ending(line);
+        if let Some(fixed) = fix_line(content, fix.condition) {
+            *line = fixed + line_ending;
+            fixed_match_indexes.push(fix.match_index);
+        } else {
+            unapplied_fix_indexes.push(fix_index);
+        }
+    }
+
+    Ok(FixedFile {
+        original_lines,
+        lines,
+        fixed_match_indexes,
+        unapplied_fix_indexes,
+    })
+}
+
+/// Rewrite lines matched by rules with replacements
+///
+/// In dry run mode, files are not modified, and unified diffs of
+/// would-be changes are printed instead, grouped by root.
+///
+/// Fixed matches are removed from the result, so only the
+/// problems which remain are reported
+pub fn apply_fixes(match_result: &mut MatchResult, dry_run: bool) {
+    let mut fixes_by_path: BTreeMap<(&Path, &Path), Vec<LineFix>> = BTreeMap::new();
+
+    for (match_index, m) in match_result.matches.iter().enumerate() {
+        if let Some(file) = &m.file
+            && let Some(line_number) = file.line
+            && let Some(condition) = get_fixing_condition(m.rule)
+        {
+            fixes_by_path
+                .entry((m.root, file.path.as_path()))
+                .or_default()
+                .push(LineFix {
+                    match_index,
+                    line_number,
+                    rule: m.rule,
+                    condition,
+                });
+        }
+    }
+
+    let mut fixed_match_indexes: HashSet<usize> = HashSet::new();
+    let mut fix_counts: BTreeMap<usize, (&Rule, usize)> = BTreeMap::new();
+    let mut current_root: Option<&Path> = None;
+
+    for ((root, path), mut fixes) in fixes_by_path {
+        fixes.sort_by_key(|fix| (fix.line_number, fix.rule.number));
+        let full_path = root.join(path);
+        let fixed_file = fix_file_lines(&full_path, &fixes);
+        if let Ok(fixed_file) = &fixed_file {
+            for fix in fixed_file
+                .unapplied_fix_indexes
+                .iter()
+                .map(|fix_index| &fixes[*fix_index])
+            {
+                eprintln!(
+                    "Warning: cannot fix {}:{} for rule \"{}\", replacement does not change the line",
+                    full_path.display(),
+                    fix.line_number + 1,
+                    fix.rule.title
+                );
+            }
+        }
+        let indexes = match fixed_file {
+            Ok(fixed_file) if fixed_file.fixed_match_indexes.is_empty() => continue,
+            Ok(fixed_file) if dry_run => {
+                if current_root != Some(root) {
+                    println!("# root {}", root.display());
+                    current_root = Some(root);
+                }
+                print!(
+                    "{}",
+                    format_unified_diff(path, &fixed_file.original_lines, &fixed_file.lines)
+                );
+                Ok(fixed_file.fixed_match_indexes)
+            }
+            Ok(fixed_file) => write_file_atomically(&full_path, &fixed_file.lines.concat())
+                .map(|_| fixed_file.fixed_match_indexes),
+            Err(err) => Err(err),
+        };
+        match indexes {
+            Ok(indexes) => {
+                for fix in fixes
+                    .iter()
+                    .filter(|fix| indexes.contains(&fix.match_index))
+                {
+                    fix_counts.entry(fix.rule.number).or_insert((fix.rule, 0)).1 += 1;
+                }
+                fixed_match_indexes.extend(indexes);
+            }
+            Err(err) => eprintln!("failed to fix {}: {}", full_path.display(), err),
+        }
+    }
+
+    for (rule, count) in fix_counts.values() {
+        if dry_run {
+            eprintln!("Would apply {} fix(es) for rule \"{}\"", count, rule.title);
+        } else {
+            eprintln!("Applied {} fix(es) for rule \"{}\"", count, rule.title);
+        }
+    }
+
+    let mut match_index = 0;
+    match_result.matches.retain(|_| {
+        match_index += 1;
+        !fixed_match_indexes.contains(&(match_index - 1))
+    });
+}
@@ -0,0 +1,147 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use std::fmt::Write;
+use std::path::Path;
+
+const CONTEXT_LINES: usize = 3;
+
+fn push_diff_line(output: &mut String, prefix: char, line: &str) {
+    output.push(prefix);
+    output.push_str(line);
+    if !line.ends_with('\n') {
+        output.push_str("\n\\ No newline at end of file\n");
+    }
+}
+
+/// Format unified diff between original and fixed lines of a file
+///
+/// Fixes never add or remove lines, so both slices are expected to
+/// have the same length, and lines are kept with their line endings.
+/// Paths are prefixed with `a/` and `b/`, so the diff may be applied
+/// with `patch -p1`.
+pub fn format_unified_diff(path: &Path, old_lines: &[String], new_lines: &[String]) -> String {
+    debug_assert_eq!(old_lines.len(), new_lines.len());
+
+    let changed: Vec<usize> = (0..old_lines.len())
+        .filter(|&i| old_lines[i] != new_lines[i])
+        .collect();
+
+    let mut output = String::new();
+    if changed.is_empty() {
+        return output;
+    }
+
+    let path = path.display();
+    writeln!(output, "--- a/{path}").unwrap();
+    writeln!(output, "+++ b/{path}").unwrap();
+
+    // group changes which are close enough to share context into hunks
+    let mut hunks: Vec<(usize, usize)> = vec![];
+    for &line in &changed {
+        match hunks.last_mut() {
+            Some((_, last)) if line - *last <= CONTEXT_LINES * 2 => *last = line,
+            _ => hunks.push((line, line)),
+        }
+    }
+
+    for (first_changed, last_changed) in hunks {
+        let start = first_changed.saturating_sub(CONTEXT_LINES);
+        let end = (last_changed + CONTEXT_LINES + 1).min(old_lines.len());
+
+        let count_lines = |lines: &[String]| -> usize {
+            lines[start..end]
+                .iter()
+                .map(|line| line.split_inclusive('\n').count().max(1))
+                .sum()
+        };
+        writeln!(
+            output,
+            "@@ -{},{} +{},{} @@",
+            start + 1,
+            count_lines(old_lines),
+            start + 1,
+            count_lines(new_lines)
+        )
+        .unwrap();
+
+        let mut i = start;
+        while i < end {
+            if old_lines[i] == new_lines[i] {
+                push_diff_line(&mut output, ' ', &old_lines[i]);
+                i += 1;
+                continue;
+            }
+
+            // consecutive changed lines are shown as a single block
+            let block_end = (i..end)
+                .find(|&j| old_lines[j] == new_lines[j])
+                .unwrap_or(end);
+            for line in &old_lines[i..block_end] {
+                push_diff_line(&mut output, '-', line);
+            }
+            for line in &new_lines[i..block_end] {
+                line.split_inclusive('\n')
+                    .for_each(|line| push_diff_line(&mut output, '+', line));
+            }
+            i = block_end;
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_lines(text: &str) -> Vec<String> {
+        text.split_inclusive('\n').map(str::to_owned).collect()
+    }
+
+    #[test]
+    fn no_changes() {
+        let lines = to_lines("a\nb\n");
+        assert_eq!(format_unified_diff(Path::new("f"), &lines, &lines), "");
+    }
+
+    #[test]
+    fn single_change() {
+        let old = to_lines("1\n2\n3\n4\n5\n6\n7\n8\n9\n");
+        let new = to_lines("1\n2\n3\n4\nX\n6\n7\n8\n9\n");
+        assert_eq!(
+            format_unified_diff(Path::new("f"), &old, &new),
+            "--- a/f\n+++ b/f\n@@ -2,7 +2,7 @@\n 2\n 3\n 4\n-5\n+X\n 6\n 7\n 8\n"
+        );
+    }
+
+    #[test]
+    fn separate_hunks() {
+        let old = to_lines("1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n");
+        let new = to_lines("X\n2\n3\n4\n5\n6\n7\n8\n9\nY\n");
+        assert_eq!(
+            format_unified_diff(Path::new("f"), &old, &new),
+            "--- a/f\n+++ b/f\n@@ -1,4 +1,4 @@\n-1\n+X\n 2\n 3\n 4\n@@ -7,4 +7,4 @@\n 7\n 8\n 9\n-10\n+Y\n"
+        );
+    }
+
+    #[test]
+    fn consecutive_changes() {
+        let old = to_lines("1\n2\n3\n");
+        let new = to_lines("1\nX\nY\n");
+        assert_eq!(
+            format_unified_diff(Path::new("f"), &old, &new),
+            "--- a/f\n+++ b/f\n@@ -1,3 +1,3 @@\n 1\n-2\n-3\n+X\n+Y\n"
+        );
+    }
+
+    #[test]
+    fn no_newline_at_end() {
+        let old = to_lines("1\n2");
+        let new = to_lines("1\nX");
+        assert_eq!(
+            format_unified_diff(Path::new("f"), &old, &new),
+            "--- a/f\n+++ b/f\n@@ -1,2 +1,2 @@\n 1\n-2\n\\ No newline at end of file\n+X\n\\ No newline at end of file\n"
+        );
+    }
+}
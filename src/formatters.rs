@@ -1,5 +1,41 @@
 // SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use crate::r#match::Match;
+use std::cmp::Ordering;
+
+pub mod checkstyle;
+pub mod csv;
+pub mod explain;
+pub mod github;
+pub mod gitlab;
 pub mod json;
+pub mod junit;
+pub mod matrix;
+pub mod report;
+pub mod sarif;
+pub mod summary;
 pub mod text;
+pub mod xml;
+
+/// Order matches by root, then by path and line, then by rule
+pub fn order_by_roots(lhs: &&Match, rhs: &&Match) -> Ordering {
+    lhs.root
+        .cmp(rhs.root)
+        .then_with(|| lhs.file.cmp(&rhs.file)) // note: sorts by both path and line
+        .then_with(|| lhs.rule.number.cmp(&rhs.rule.number))
+}
+
+/// Order matches by rule, then by root, path and line
+pub fn order_by_rules(lhs: &&Match, rhs: &&Match) -> Ordering {
+    lhs.rule
+        .number
+        .cmp(&rhs.rule.number)
+        .then_with(|| lhs.root.cmp(rhs.root))
+        .then_with(|| lhs.file.cmp(&rhs.file)) // note: sorts by both path and line
+}
+
+/// Sort matches in stable order common to all output formats
+pub fn sort_matches_by_root(matches: &mut [&Match]) {
+    matches.sort_unstable_by(order_by_roots);
+}
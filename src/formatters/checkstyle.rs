@@ -0,0 +1,67 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::formatters::sort_matches_by_root;
+use crate::formatters::xml::escape_xml;
+use crate::r#match::{Match, MatchResult};
+use crate::ruleset::{Rule, Severity};
+
+const CHECKSTYLE_VERSION: &str = "4.3";
+
+fn get_severity(rule: &Rule) -> &'static str {
+    match rule.get_severity() {
+        Some(Severity::Error) => "error",
+        Some(Severity::Warning) | None => "warning",
+        Some(Severity::Minor) => "info",
+    }
+}
+
+/// Get name of the file match is attached to; matches without
+/// a file are attached to the root directory
+fn get_file_name(m: &Match) -> String {
+    if let Some(file) = &m.file {
+        m.root.join(file.path.as_path()).display().to_string()
+    } else {
+        m.root.display().to_string()
+    }
+}
+
+pub fn format_matches(match_result: &MatchResult) {
+    let mut matches: Vec<&Match> = match_result.matches.iter().collect();
+    sort_matches_by_root(&mut matches);
+
+    println!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+    println!("<checkstyle version=\"{CHECKSTYLE_VERSION}\">");
+
+    let mut current_file_name: Option<String> = None;
+
+    for m in matches {
+        let file_name = get_file_name(m);
+        if current_file_name.as_ref() != Some(&file_name) {
+            if current_file_name.is_some() {
+                println!("  </file>");
+            }
+            println!("  <file name=\"{}\">", escape_xml(&file_name));
+            current_file_name = Some(file_name);
+        }
+
+        let line = m
+            .file
+            .as_ref()
+            .and_then(|file| file.line)
+            .map(|line| format!(" line=\"{}\"", line + 1))
+            .unwrap_or_default();
+        println!(
+            "    <error{} severity=\"{}\" message=\"{}\" source=\"omnilinter.{}\"/>",
+            line,
+            get_severity(m.rule),
+            escape_xml(&m.rule.title),
+            escape_xml(m.rule.id.as_deref().unwrap_or(&m.rule.title))
+        );
+    }
+
+    if current_file_name.is_some() {
+        println!("  </file>");
+    }
+    println!("</checkstyle>");
+}
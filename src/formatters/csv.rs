@@ -0,0 +1,89 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::formatters::sort_matches_by_root;
+use crate::r#match::{Match, MatchResult};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Format {
+    Csv,
+    Tsv,
+}
+
+impl Format {
+    fn get_separator(self) -> char {
+        match self {
+            Format::Csv => ',',
+            Format::Tsv => '\t',
+        }
+    }
+}
+
+/// Quote field if it contains separator, quotes or line breaks
+fn quote_field(field: &str, separator: char) -> String {
+    if field.contains([separator, '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+pub fn format_row(fields: &[&str], separator: char) -> String {
+    fields
+        .iter()
+        .map(|field| quote_field(field, separator))
+        .collect::<Vec<_>>()
+        .join(&separator.to_string())
+}
+
+pub fn format_matches(match_result: &MatchResult, format: Format) {
+    let separator = format.get_separator();
+
+    let mut matches: Vec<&Match> = match_result.matches.iter().collect();
+    sort_matches_by_root(&mut matches);
+
+    println!(
+        "{}",
+        format_row(&["root", "path", "line", "rule", "tags"], separator)
+    );
+
+    for m in matches {
+        let root = m.root.display().to_string();
+        let path = m
+            .file
+            .as_ref()
+            .map(|file| file.path.display().to_string())
+            .unwrap_or_default();
+        let line = m
+            .file
+            .as_ref()
+            .and_then(|file| file.line)
+            .map(|line| (line + 1).to_string())
+            .unwrap_or_default();
+        let mut tags: Vec<&str> = m.rule.tags.iter().map(|tag| tag.as_str()).collect();
+        tags.sort();
+
+        println!(
+            "{}",
+            format_row(
+                &[&root, &path, &line, &m.rule.title, &tags.join(",")],
+                separator
+            )
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quoting() {
+        assert_eq!(quote_field("plain text", ','), "plain text");
+        assert_eq!(quote_field("a,b", ','), "\"a,b\"");
+        assert_eq!(quote_field("a,b", '\t'), "a,b");
+        assert_eq!(quote_field("a\tb", '\t'), "\"a\tb\"");
+        assert_eq!(quote_field("say \"hi\"", ','), "\"say \"\"hi\"\"\"");
+        assert_eq!(quote_field("a\nb", '\t'), "\"a\nb\"");
+    }
+}
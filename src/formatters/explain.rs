@@ -0,0 +1,264 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::config::dumper::{
+    format_content_condition, format_match_count_condition, format_path_condition,
+};
+use crate::explanation::{ContentConditionOutcome, Explanation, FileTrace, PathConditionTrace};
+use crate::r#match::MatchResult;
+use crate::ruleset::{
+    ConditionLogic, ContentCondition, ContentConditionNode, GlobCondition, RegexCondition,
+};
+use colored::*;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Status {
+    Satisfied,
+    Failed,
+    Unknown,
+}
+
+fn get_mark(status: Status) -> String {
+    match status {
+        Status::Satisfied => "[+]".green().to_string(),
+        Status::Failed => "[-]".red().to_string(),
+        Status::Unknown => "[?]".yellow().to_string(),
+    }
+}
+
+fn format_line_numbers(lines: &[u64]) -> String {
+    lines
+        .iter()
+        .map(|line| (line + 1).to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn get_content_condition_status(
+    node: &ContentConditionNode,
+    outcome: &ContentConditionOutcome,
+) -> Status {
+    match (&node.condition, outcome) {
+        (_, ContentConditionOutcome::NotChecked) => Status::Unknown,
+        (
+            ContentCondition::Match(RegexCondition {
+                count: Some(count_condition),
+                ..
+            }),
+            ContentConditionOutcome::Matched(lines),
+        ) => {
+            if count_condition.check(lines.len() as u64) {
+                Status::Satisfied
+            } else {
+                Status::Failed
+            }
+        }
+        (
+            ContentCondition::Match(RegexCondition {
+                count: Some(count_condition),
+                ..
+            }),
+            ContentConditionOutcome::NotMatched,
+        ) => {
+            if count_condition.check(0) {
+                Status::Satisfied
+            } else {
+                Status::Failed
+            }
+        }
+        (ContentCondition::Match(_), ContentConditionOutcome::Matched(_)) => Status::Satisfied,
+        (ContentCondition::NoMatch(_), ContentConditionOutcome::NotMatched) => Status::Satisfied,
+        (ContentCondition::Size(condition), ContentConditionOutcome::Value(value))
+        | (ContentCondition::Lines(condition), ContentConditionOutcome::Value(value)) => {
+            if condition.check(*value) {
+                Status::Satisfied
+            } else {
+                Status::Failed
+            }
+        }
+        (ContentCondition::Lines(condition), ContentConditionOutcome::AtLeast(value)) => {
+            if condition.check_for_this_and_above(*value) {
+                Status::Satisfied
+            } else {
+                Status::Unknown
+            }
+        }
+        _ => Status::Failed,
+    }
+}
+
+fn describe_content_condition_outcome(
+    node: &ContentConditionNode,
+    outcome: &ContentConditionOutcome,
+) -> String {
+    match (&node.condition, outcome) {
+        (_, ContentConditionOutcome::NotChecked) => "not checked".to_owned(),
+        (ContentCondition::NoMatch(_), ContentConditionOutcome::Matched(lines)) => {
+            format!("matched at line {}", format_line_numbers(lines))
+        }
+        (_, ContentConditionOutcome::Matched(lines)) if lines.len() == 1 => {
+            format!("matched at line {}", format_line_numbers(lines))
+        }
+        (_, ContentConditionOutcome::Matched(lines)) => {
+            format!("matched at lines {}", format_line_numbers(lines))
+        }
+        (_, ContentConditionOutcome::NotMatched) => "not matched".to_owned(),
+        (ContentCondition::Size(_), ContentConditionOutcome::Value(value)) => {
+            format!("size is {value} bytes")
+        }
+        (_, ContentConditionOutcome::Value(value)) => format!("file has {value} lines"),
+        (_, ContentConditionOutcome::AtLeast(value)) => {
+            format!("file has at least {value} lines")
+        }
+    }
+}
+
+fn print_file_trace(path_condition: &GlobCondition, file_trace: &FileTrace) {
+    println!(
+        "      {} {}",
+        get_mark(if file_trace.is_matched {
+            Status::Satisfied
+        } else {
+            Status::Failed
+        }),
+        file_trace.path.display().to_string().bold()
+    );
+    for (node, outcome) in path_condition
+        .content_conditions
+        .iter()
+        .zip(file_trace.outcomes.iter())
+    {
+        let mut condition = format_content_condition(node);
+        if let ContentCondition::Match(regex_condition) = &node.condition
+            && let Some(count_condition) = &regex_condition.count
+        {
+            condition += ", ";
+            condition += &format_match_count_condition(count_condition);
+        }
+        println!(
+            "        {} {}: {}",
+            get_mark(get_content_condition_status(node, outcome)),
+            condition,
+            describe_content_condition_outcome(node, outcome)
+        );
+    }
+}
+
+fn get_path_condition_status(
+    path_condition: &GlobCondition,
+    trace: &PathConditionTrace,
+    is_walk_interrupted: bool,
+) -> Status {
+    match path_condition.logic {
+        ConditionLogic::Negative if trace.matched_paths_count == 0 => Status::Satisfied,
+        ConditionLogic::Negative => Status::Failed,
+        ConditionLogic::Positive if trace.matched_paths_count == 0 && is_walk_interrupted => {
+            Status::Unknown
+        }
+        ConditionLogic::Positive if trace.matched_paths_count == 0 => Status::Failed,
+        ConditionLogic::Positive if path_condition.content_conditions.is_empty() => {
+            Status::Satisfied
+        }
+        ConditionLogic::Positive if trace.files.iter().any(|file| file.is_matched) => {
+            Status::Satisfied
+        }
+        ConditionLogic::Positive if trace.files.is_empty() => Status::Unknown,
+        ConditionLogic::Positive => Status::Failed,
+    }
+}
+
+fn describe_path_condition_outcome(
+    path_condition: &GlobCondition,
+    trace: &PathConditionTrace,
+    is_walk_interrupted: bool,
+) -> String {
+    match (&path_condition.logic, &trace.first_matched_path) {
+        (_, None) if is_walk_interrupted => "no paths matched before evaluation stopped".to_owned(),
+        (_, None) => "no paths matched".to_owned(),
+        (ConditionLogic::Negative, Some(path)) => format!("matched {}", path.display()),
+        (ConditionLogic::Positive, Some(path)) if trace.matched_paths_count == 1 => {
+            format!("matched {}", path.display())
+        }
+        (ConditionLogic::Positive, Some(path)) => format!(
+            "matched {} paths, first is {}",
+            trace.matched_paths_count,
+            path.display()
+        ),
+    }
+}
+
+fn print_explanation(explanation: &Explanation) {
+    println!(
+        "  {} {}: {}",
+        get_mark(if explanation.is_matched {
+            Status::Satisfied
+        } else {
+            Status::Failed
+        }),
+        explanation.rule.title,
+        if explanation.is_matched {
+            "matched"
+        } else {
+            "not matched"
+        }
+    );
+
+    if explanation.rule.path_conditions.is_empty() {
+        println!("    (rule has no conditions and always matches)");
+    }
+
+    // rule evaluation stops as soon as any nofiles condition fails,
+    // so other path conditions may not have seen all the paths
+    let is_walk_interrupted = explanation
+        .rule
+        .path_conditions
+        .iter()
+        .zip(explanation.path_conditions.iter())
+        .any(|(path_condition, trace)| {
+            path_condition.logic == ConditionLogic::Negative && trace.matched_paths_count > 0
+        });
+
+    for (path_condition, trace) in explanation
+        .rule
+        .path_conditions
+        .iter()
+        .zip(explanation.path_conditions.iter())
+    {
+        println!(
+            "    {} {}: {}",
+            get_mark(get_path_condition_status(
+                path_condition,
+                trace,
+                is_walk_interrupted
+            )),
+            format_path_condition(path_condition),
+            describe_path_condition_outcome(path_condition, trace, is_walk_interrupted)
+        );
+        if !path_condition.content_conditions.is_empty() && trace.files.is_empty() {
+            println!("      (content conditions were not checked)");
+        }
+        let mut file_traces: Vec<&FileTrace> = trace.files.iter().collect();
+        file_traces.sort_unstable_by(|lhs, rhs| lhs.path.cmp(&rhs.path));
+        for file_trace in file_traces {
+            print_file_trace(path_condition, file_trace);
+        }
+    }
+}
+
+pub fn format_explanations(match_result: &MatchResult) {
+    let mut explanations: Vec<&Explanation> = match_result.explanations.iter().collect();
+    explanations.sort_unstable_by(|lhs, rhs| {
+        lhs.root
+            .cmp(rhs.root)
+            .then_with(|| lhs.rule.number.cmp(&rhs.rule.number))
+    });
+
+    let mut prev_root = None;
+    for explanation in explanations {
+        if prev_root != Some(explanation.root) {
+            println!("{}", explanation.root.display().to_string().yellow().bold());
+            prev_root = Some(explanation.root);
+        }
+        print_explanation(explanation);
+    }
+}
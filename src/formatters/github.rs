@@ -0,0 +1,87 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::formatters::sort_matches_by_root;
+use crate::r#match::{Match, MatchResult};
+use crate::ruleset::{Rule, Severity};
+use std::path::{Path, PathBuf};
+
+fn get_command(rule: &Rule) -> &'static str {
+    match rule.get_severity() {
+        Some(Severity::Error) => "error",
+        Some(Severity::Warning) | None => "warning",
+        Some(Severity::Minor) => "notice",
+    }
+}
+
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Directory annotation paths are relative to: `GITHUB_WORKSPACE`
+/// when running in GitHub Actions, current directory otherwise
+fn get_workspace() -> Option<PathBuf> {
+    std::env::var_os("GITHUB_WORKSPACE")
+        .map(PathBuf::from)
+        .or_else(|| std::env::current_dir().ok())
+        .and_then(|path| std::path::absolute(path).ok())
+}
+
+fn get_workspace_relative_path(path: &Path, workspace: Option<&Path>) -> String {
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    workspace
+        .and_then(|workspace| path.strip_prefix(workspace).ok())
+        .unwrap_or(&path)
+        .display()
+        .to_string()
+        .replace('\\', "/")
+}
+
+fn format_match(m: &Match, workspace: Option<&Path>) -> String {
+    let mut properties = vec![];
+    let mut message = m.rule.title.clone();
+
+    if let Some(file) = &m.file {
+        let path = m.root.join(file.path.as_path());
+        properties.push(format!(
+            "file={}",
+            escape_property(&get_workspace_relative_path(&path, workspace))
+        ));
+        if let Some(line) = file.line {
+            properties.push(format!("line={}", line + 1));
+        }
+    } else {
+        message += &format!(" (in {})", get_workspace_relative_path(m.root, workspace));
+    }
+
+    let title = match &m.rule.id {
+        Some(id) => format!("omnilinter [{id}]"),
+        None => "omnilinter".to_owned(),
+    };
+    properties.push(format!("title={}", escape_property(&title)));
+
+    format!(
+        "::{} {}::{}",
+        get_command(m.rule),
+        properties.join(","),
+        escape_data(&message)
+    )
+}
+
+/// Print GitHub Actions workflow commands which produce inline annotations
+pub fn format_matches(match_result: &MatchResult) {
+    let workspace = get_workspace();
+
+    let mut matches: Vec<&Match> = match_result.matches.iter().collect();
+    sort_matches_by_root(&mut matches);
+
+    for m in matches {
+        println!("{}", format_match(m, workspace.as_deref()));
+    }
+}
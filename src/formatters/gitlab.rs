@@ -0,0 +1,91 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::fingerprint::{fnv1a, get_line_fingerprint};
+use crate::formatters::sort_matches_by_root;
+use crate::r#match::{Match, MatchResult};
+use crate::ruleset::{Rule, Severity};
+use std::collections::HashMap;
+
+#[derive(serde::Serialize)]
+struct Issue<'a> {
+    description: &'a str,
+    check_name: &'a str,
+    fingerprint: String,
+    severity: &'static str,
+    location: Location,
+}
+
+#[derive(serde::Serialize)]
+struct Location {
+    path: String,
+    lines: Lines,
+}
+
+#[derive(serde::Serialize)]
+struct Lines {
+    begin: u64,
+}
+
+fn get_severity(rule: &Rule) -> &'static str {
+    match rule.get_severity() {
+        Some(Severity::Error) => "critical",
+        Some(Severity::Warning) | None => "major",
+        Some(Severity::Minor) => "minor",
+    }
+}
+
+fn get_check_name(rule: &Rule) -> &str {
+    rule.id.as_deref().unwrap_or(&rule.title)
+}
+
+/// Produce GitLab Code Quality report
+///
+/// Fingerprints are derived from the rule, root-relative path and content
+/// of the matched line rather than its number, so issues are tracked
+/// between pipelines even if lines are moved around. Identical issues in
+/// the same file are distinguished by their order.
+pub fn format_matches(match_result: &MatchResult) {
+    let mut matches: Vec<&Match> = match_result.matches.iter().collect();
+    sort_matches_by_root(&mut matches);
+
+    let mut occurrences: HashMap<String, usize> = HashMap::new();
+
+    let issues: Vec<Issue> = matches
+        .into_iter()
+        .map(|m| {
+            let path = m
+                .file
+                .as_ref()
+                .map_or_else(|| ".".to_owned(), |file| file.path.display().to_string());
+
+            let identity = format!(
+                "{}\0{}\0{}",
+                get_check_name(m.rule),
+                path,
+                get_line_fingerprint(m).unwrap_or_default()
+            );
+            let occurrence = occurrences.entry(identity.clone()).or_default();
+            *occurrence += 1;
+            let fingerprint = format!(
+                "{:016x}",
+                fnv1a(format!("{}\0{}", identity, occurrence).as_bytes())
+            );
+
+            Issue {
+                description: &m.rule.title,
+                check_name: get_check_name(m.rule),
+                fingerprint,
+                severity: get_severity(m.rule),
+                location: Location {
+                    path,
+                    lines: Lines {
+                        begin: m.file.as_ref().and_then(|file| file.line).unwrap_or(0) + 1,
+                    },
+                },
+            }
+        })
+        .collect();
+
+    println!("{}", serde_json::to_string_pretty(&issues).unwrap());
+}
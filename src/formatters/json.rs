@@ -1,7 +1,10 @@
 // SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use crate::formatters::sort_matches_by_root;
+use crate::formatters::summary::Summary;
 use crate::r#match::MatchResult;
+use std::io::Write;
 
 #[derive(serde::Serialize)]
 struct Match<'a> {
@@ -11,27 +14,72 @@ struct Match<'a> {
     file: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     line: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    text: Option<&'a str>,
+}
+
+impl<'a> Match<'a> {
+    fn new(m: &'a crate::r#match::Match) -> Self {
+        let span = m.file.as_ref().and_then(|file| file.span.as_ref());
+        Match {
+            message: &m.rule.title,
+            root: m.root.display().to_string(),
+            file: m.file.as_ref().map(|file| file.path.display().to_string()),
+            line: m
+                .file
+                .as_ref()
+                .and_then(|file| file.line.map(|line| line + 1)),
+            column: span.map(|span| span.start + 1),
+            end_column: span.map(|span| span.end + 1),
+            text: span.map(|span| span.text.as_str()),
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct MatchesWithStats<'a> {
+    matches: Vec<Match<'a>>,
+    stats: &'a Summary<'a>,
 }
 
-pub fn format_matches(match_result: &MatchResult) {
+/// Print matches as JSON array, or, if summary is requested,
+/// as an object with `matches` array and `stats` object
+pub fn format_matches(match_result: &MatchResult, summary: Option<&Summary>) {
+    if let Some(summary) = summary {
+        let output = MatchesWithStats {
+            matches: match_result.matches.iter().map(Match::new).collect(),
+            stats: summary,
+        };
+        println!("{}", serde_json::to_string_pretty(&output).unwrap());
+        return;
+    }
+
     println!("[");
     for (n, m) in match_result.matches.iter().enumerate() {
         if n > 0 {
             println!(",");
         }
-        print!(
-            "{}",
-            serde_json::to_string_pretty(&Match {
-                message: &m.rule.title,
-                root: m.root.display().to_string(),
-                file: m.file.as_ref().map(|file| file.path.display().to_string()),
-                line: m
-                    .file
-                    .as_ref()
-                    .and_then(|file| file.line.map(|line| line + 1)),
-            })
-            .unwrap()
-        );
+        print!("{}", serde_json::to_string_pretty(&Match::new(m)).unwrap());
     }
     println!("]");
 }
+
+/// Print matches as JSON Lines, one object per line
+///
+/// This is used to stream matches as soon as each root is processed,
+/// so all matches of a root are printed at once and are not interleaved
+/// with ones from other roots
+pub fn format_matches_as_lines(match_result: &MatchResult) {
+    let mut matches: Vec<_> = match_result.matches.iter().collect();
+    sort_matches_by_root(&mut matches);
+
+    let mut stdout = std::io::stdout().lock();
+    for m in matches {
+        writeln!(stdout, "{}", serde_json::to_string(&Match::new(m)).unwrap()).unwrap();
+    }
+    stdout.flush().unwrap();
+}
@@ -0,0 +1,87 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::formatters::xml::escape_xml;
+use crate::r#match::{Match, MatchResult};
+use crate::ruleset::Rule;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+fn format_location(m: &Match) -> String {
+    match &m.file {
+        Some(file) => {
+            let path = m.root.join(file.path.as_path()).display().to_string();
+            match file.line {
+                Some(line) => format!("{}:{}", path, line + 1),
+                None => path,
+            }
+        }
+        None => m.root.display().to_string(),
+    }
+}
+
+/// Print JUnit XML report, in which each root is a test suite, and each
+/// rule is a test case which fails if the rule has matches in that root
+///
+/// All rules are listed for each root, so the report shows which rules
+/// were checked even if they did not match.
+pub fn format_matches(rules: &[Rule], roots: &[PathBuf], match_result: &MatchResult) {
+    let mut matches_by_root_and_rule: HashMap<(&Path, usize), Vec<&Match>> = HashMap::new();
+    for m in &match_result.matches {
+        matches_by_root_and_rule
+            .entry((m.root, m.rule.number))
+            .or_default()
+            .push(m);
+    }
+    matches_by_root_and_rule
+        .values_mut()
+        .for_each(|matches| matches.sort_by(|lhs, rhs| lhs.file.cmp(&rhs.file)));
+
+    let mut roots: Vec<&Path> = roots.iter().map(|root| root.as_path()).collect();
+    roots.sort();
+    roots.dedup();
+
+    let count_failures = |root: &Path| {
+        rules
+            .iter()
+            .filter(|rule| matches_by_root_and_rule.contains_key(&(root, rule.number)))
+            .count()
+    };
+    let total_failures: usize = roots.iter().map(|root| count_failures(root)).sum();
+
+    println!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+    println!(
+        "<testsuites name=\"omnilinter\" tests=\"{}\" failures=\"{}\">",
+        rules.len() * roots.len(),
+        total_failures
+    );
+
+    for root in roots {
+        let root_name = escape_xml(&root.display().to_string());
+        println!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">",
+            root_name,
+            rules.len(),
+            count_failures(root)
+        );
+        for rule in rules {
+            let title = escape_xml(&rule.title);
+            let Some(matches) = matches_by_root_and_rule.get(&(root, rule.number)) else {
+                println!("    <testcase name=\"{title}\" classname=\"{root_name}\"/>");
+                continue;
+            };
+            println!("    <testcase name=\"{title}\" classname=\"{root_name}\">");
+            let locations: Vec<String> = matches.iter().map(|m| format_location(m)).collect();
+            println!(
+                "      <failure message=\"{} match(es)\" type=\"{}\">{}</failure>",
+                matches.len(),
+                escape_xml(rule.id.as_deref().unwrap_or(&rule.title)),
+                escape_xml(&locations.join("\n"))
+            );
+            println!("    </testcase>");
+        }
+        println!("  </testsuite>");
+    }
+
+    println!("</testsuites>");
+}
@@ -0,0 +1,204 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::formatters::csv::format_row;
+use crate::r#match::MatchResult;
+use crate::ruleset::Rule;
+use colored::*;
+use std::collections::{BTreeMap, HashMap};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+/// Match counts with roots as rows and rules as columns
+///
+/// All checked roots are listed, so roots without matches are visible,
+/// but only rules which matched anything get a column.
+struct Matrix<'a> {
+    roots: Vec<&'a Path>,
+    rules: Vec<&'a Rule>,
+    counts: HashMap<(&'a Path, usize), usize>,
+}
+
+impl<'a> Matrix<'a> {
+    fn new(roots: &'a [PathBuf], match_result: &'a MatchResult) -> Self {
+        let mut roots: Vec<&Path> = roots.iter().map(|root| root.as_path()).collect();
+        roots.sort();
+        roots.dedup();
+
+        let mut rules: BTreeMap<usize, &Rule> = BTreeMap::new();
+        let mut counts: HashMap<(&Path, usize), usize> = HashMap::new();
+        for m in &match_result.matches {
+            rules.insert(m.rule.number, m.rule);
+            *counts.entry((m.root, m.rule.number)).or_default() += 1;
+        }
+
+        Self {
+            roots,
+            rules: rules.into_values().collect(),
+            counts,
+        }
+    }
+
+    fn get_count(&self, root: &Path, rule: &Rule) -> usize {
+        self.counts
+            .get(&(root, rule.number))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn get_root_total(&self, root: &Path) -> usize {
+        self.rules
+            .iter()
+            .map(|rule| self.get_count(root, rule))
+            .sum()
+    }
+
+    fn get_rule_total(&self, rule: &Rule) -> usize {
+        self.roots
+            .iter()
+            .map(|root| self.get_count(root, rule))
+            .sum()
+    }
+
+    fn get_total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Table cells, including header row and totals row and column
+    fn get_rows(&self) -> Vec<Vec<String>> {
+        let mut rows = vec![];
+
+        let mut header = vec!["root".to_owned()];
+        header.extend(
+            self.rules
+                .iter()
+                .map(|rule| rule.id.clone().unwrap_or_else(|| rule.title.clone())),
+        );
+        header.push("total".to_owned());
+        rows.push(header);
+
+        for root in &self.roots {
+            let mut row = vec![root.display().to_string()];
+            row.extend(
+                self.rules
+                    .iter()
+                    .map(|rule| self.get_count(root, rule).to_string()),
+            );
+            row.push(self.get_root_total(root).to_string());
+            rows.push(row);
+        }
+
+        let mut totals = vec!["total".to_owned()];
+        totals.extend(
+            self.rules
+                .iter()
+                .map(|rule| self.get_rule_total(rule).to_string()),
+        );
+        totals.push(self.get_total().to_string());
+        rows.push(totals);
+
+        rows
+    }
+}
+
+fn format_csv(rows: &[Vec<String>]) -> String {
+    rows.iter()
+        .map(|row| {
+            let fields: Vec<&str> = row.iter().map(|field| field.as_str()).collect();
+            format_row(&fields, ',') + "\n"
+        })
+        .collect()
+}
+
+fn format_aligned(rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = vec![0; rows[0].len()];
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let last_row = rows.len() - 1;
+    let last_column = widths.len() - 1;
+
+    let mut output = String::new();
+    for (nrow, row) in rows.iter().enumerate() {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(ncolumn, (cell, &width))| {
+                // pad before coloring, as escape sequences would break alignment
+                if ncolumn == 0 {
+                    let cell = format!("{cell:<width$}");
+                    if nrow == 0 || nrow == last_row {
+                        cell.bold().to_string()
+                    } else {
+                        cell.yellow().bold().to_string()
+                    }
+                } else {
+                    let is_zero = cell == "0";
+                    let cell = format!("{cell:>width$}");
+                    if nrow == 0 || nrow == last_row || ncolumn == last_column {
+                        cell.bold().to_string()
+                    } else if is_zero {
+                        cell.dimmed().to_string()
+                    } else {
+                        cell.red().to_string()
+                    }
+                }
+            })
+            .collect();
+        output.push_str(cells.join("  ").trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+/// Print table of match counts per root and rule, with totals
+///
+/// The table is aligned for reading in a terminal, and is printed
+/// as CSV when output is redirected.
+pub fn format_matches(roots: &[PathBuf], match_result: &MatchResult) {
+    let rows = Matrix::new(roots, match_result).get_rows();
+    if std::io::stdout().is_terminal() {
+        print!("{}", format_aligned(&rows));
+    } else {
+        print!("{}", format_csv(&rows));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_rows(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn aligned() {
+        colored::control::set_override(false);
+        let rows = to_rows(&[
+            &["root", "rule-a", "b", "total"],
+            &["long/root", "12", "0", "12"],
+            &["x", "1", "1", "2"],
+            &["total", "13", "1", "14"],
+        ]);
+        assert_eq!(
+            format_aligned(&rows),
+            "root       rule-a  b  total\n\
+             long/root      12  0     12\n\
+             x               1  1      2\n\
+             total          13  1     14\n"
+        );
+    }
+
+    #[test]
+    fn csv() {
+        let rows = to_rows(&[&["root", "a, b", "total"], &["total", "0", "0"]]);
+        assert_eq!(format_csv(&rows), "root,\"a, b\",total\ntotal,0,0\n");
+    }
+}
@@ -0,0 +1,259 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::formatters::sort_matches_by_root;
+use crate::formatters::xml::escape_xml;
+use crate::r#match::{Match, MatchResult};
+use crate::ruleset::Rule;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+const TITLE: &str = "omnilinter report";
+
+const HTML_STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; margin-bottom: 1em; }
+th, td { border: 1px solid #ccc; padding: 0.25em 0.75em; text-align: left; }
+td.count { text-align: right; }
+summary { cursor: pointer; font-weight: bold; }";
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+/// Matches grouped by root the same way as in by-root text output,
+/// along with summary counts
+struct Report<'a> {
+    total_count: usize,
+    rule_counts: Vec<(&'a Rule, usize)>,
+    roots: Vec<(&'a Path, Vec<&'a Match<'a>>)>,
+}
+
+impl<'a> Report<'a> {
+    fn new(match_result: &'a MatchResult) -> Self {
+        let mut matches: Vec<&Match> = match_result.matches.iter().collect();
+        sort_matches_by_root(&mut matches);
+
+        let mut rule_counts: BTreeMap<usize, (&Rule, usize)> = BTreeMap::new();
+        for m in &matches {
+            rule_counts.entry(m.rule.number).or_insert((m.rule, 0)).1 += 1;
+        }
+
+        Self {
+            total_count: matches.len(),
+            rule_counts: rule_counts.into_values().collect(),
+            roots: matches
+                .chunk_by(|lhs, rhs| lhs.root == rhs.root)
+                .map(|chunk| (chunk[0].root, chunk.to_vec()))
+                .collect(),
+        }
+    }
+}
+
+fn get_location(m: &Match) -> Option<String> {
+    let file = m.file.as_ref()?;
+    Some(match file.line {
+        Some(line) => format!("{}:{}", file.path.display(), line + 1),
+        None => file.path.display().to_string(),
+    })
+}
+
+/// Escape characters which have special meaning in Markdown inline text
+fn escape_markdown(text: &str) -> String {
+    let mut res = String::with_capacity(text.len());
+    for (n, c) in text.chars().enumerate() {
+        // these only matter at the start of the text, where they could
+        // turn list item into a heading or nested list
+        if "\\`*_[]<>|~&".contains(c) || (n == 0 && "#+-".contains(c)) {
+            res.push('\\');
+        }
+        res.push(c);
+    }
+    res
+}
+
+/// Wrap text into code span, using long enough backtick fence
+fn format_markdown_code(text: &str) -> String {
+    let mut fence = String::from("`");
+    while text.contains(&fence) {
+        fence.push('`');
+    }
+    let padding = if text.starts_with('`') || text.ends_with('`') {
+        " "
+    } else {
+        ""
+    };
+    format!("{fence}{padding}{text}{padding}{fence}")
+}
+
+fn format_markdown(report: &Report) -> String {
+    let mut output = String::new();
+
+    writeln!(output, "# {TITLE}\n").unwrap();
+    writeln!(
+        output,
+        "{} match(es) in {} root(s).\n",
+        report.total_count,
+        report.roots.len()
+    )
+    .unwrap();
+
+    if report.total_count == 0 {
+        return output;
+    }
+
+    writeln!(output, "## Matches per rule\n").unwrap();
+    writeln!(output, "| Rule | Matches |\n| --- | ---: |").unwrap();
+    for (rule, count) in &report.rule_counts {
+        writeln!(output, "| {} | {} |", escape_markdown(&rule.title), count).unwrap();
+    }
+    writeln!(output, "| **Total** | **{}** |\n", report.total_count).unwrap();
+
+    writeln!(output, "## Matches per root\n").unwrap();
+    writeln!(output, "| Root | Matches |\n| --- | ---: |").unwrap();
+    for (root, matches) in &report.roots {
+        let root = root.display().to_string();
+        writeln!(output, "| {} | {} |", escape_markdown(&root), matches.len()).unwrap();
+    }
+    writeln!(output, "| **Total** | **{}** |\n", report.total_count).unwrap();
+
+    writeln!(output, "## Matches\n").unwrap();
+    for (root, matches) in &report.roots {
+        // contents of summary are HTML rather than Markdown
+        writeln!(
+            output,
+            "<details>\n<summary>{} ({})</summary>\n",
+            escape_xml(&root.display().to_string()),
+            matches.len()
+        )
+        .unwrap();
+        for m in matches {
+            let title = escape_markdown(&m.rule.title);
+            match get_location(m) {
+                Some(location) => {
+                    writeln!(output, "- {}: {}", format_markdown_code(&location), title).unwrap()
+                }
+                None => writeln!(output, "- {title}").unwrap(),
+            }
+        }
+        writeln!(output, "\n</details>\n").unwrap();
+    }
+
+    output
+}
+
+fn format_html(report: &Report) -> String {
+    let mut output = String::new();
+
+    writeln!(output, "<!DOCTYPE html>\n<html>\n<head>").unwrap();
+    writeln!(output, "<meta charset=\"utf-8\">").unwrap();
+    writeln!(output, "<title>{TITLE}</title>").unwrap();
+    writeln!(output, "<style>\n{HTML_STYLE}\n</style>").unwrap();
+    writeln!(output, "</head>\n<body>\n<h1>{TITLE}</h1>").unwrap();
+    writeln!(
+        output,
+        "<p>{} match(es) in {} root(s).</p>",
+        report.total_count,
+        report.roots.len()
+    )
+    .unwrap();
+
+    if report.total_count > 0 {
+        writeln!(output, "<h2>Matches per rule</h2>\n<table>").unwrap();
+        writeln!(output, "<tr><th>Rule</th><th>Matches</th></tr>").unwrap();
+        for (rule, count) in &report.rule_counts {
+            writeln!(
+                output,
+                "<tr><td>{}</td><td class=\"count\">{}</td></tr>",
+                escape_xml(&rule.title),
+                count
+            )
+            .unwrap();
+        }
+        writeln!(
+            output,
+            "<tr><th>Total</th><th class=\"count\">{}</th></tr>\n</table>",
+            report.total_count
+        )
+        .unwrap();
+
+        writeln!(output, "<h2>Matches per root</h2>\n<table>").unwrap();
+        writeln!(output, "<tr><th>Root</th><th>Matches</th></tr>").unwrap();
+        for (root, matches) in &report.roots {
+            writeln!(
+                output,
+                "<tr><td>{}</td><td class=\"count\">{}</td></tr>",
+                escape_xml(&root.display().to_string()),
+                matches.len()
+            )
+            .unwrap();
+        }
+        writeln!(
+            output,
+            "<tr><th>Total</th><th class=\"count\">{}</th></tr>\n</table>",
+            report.total_count
+        )
+        .unwrap();
+
+        writeln!(output, "<h2>Matches</h2>").unwrap();
+        for (root, matches) in &report.roots {
+            writeln!(
+                output,
+                "<details>\n<summary>{} ({})</summary>\n<ul>",
+                escape_xml(&root.display().to_string()),
+                matches.len()
+            )
+            .unwrap();
+            for m in matches {
+                let title = escape_xml(&m.rule.title);
+                match get_location(m) {
+                    Some(location) => writeln!(
+                        output,
+                        "<li><code>{}</code>: {}</li>",
+                        escape_xml(&location),
+                        title
+                    )
+                    .unwrap(),
+                    None => writeln!(output, "<li>{title}</li>").unwrap(),
+                }
+            }
+            writeln!(output, "</ul>\n</details>").unwrap();
+        }
+    }
+
+    writeln!(output, "</body>\n</html>").unwrap();
+
+    output
+}
+
+pub fn format_matches(match_result: &MatchResult, format: Format) {
+    let report = Report::new(match_result);
+    match format {
+        Format::Markdown => print!("{}", format_markdown(&report)),
+        Format::Html => print!("{}", format_html(&report)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_escaping() {
+        assert_eq!(escape_markdown("plain text"), "plain text");
+        assert_eq!(escape_markdown("a|b"), "a\\|b");
+        assert_eq!(escape_markdown("*_x_*"), "\\*\\_x\\_\\*");
+        assert_eq!(escape_markdown("<b>"), "\\<b\\>");
+        assert_eq!(escape_markdown("# not-a-heading"), "\\# not-a-heading");
+    }
+
+    #[test]
+    fn markdown_code() {
+        assert_eq!(format_markdown_code("a.py:1"), "`a.py:1`");
+        assert_eq!(format_markdown_code("a`b.py"), "``a`b.py``");
+        assert_eq!(format_markdown_code("`a.py"), "`` `a.py ``");
+    }
+}
@@ -0,0 +1,280 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::formatters::sort_matches_by_root;
+use crate::r#match::{LineSpan, Match, MatchResult};
+use crate::ruleset::{Rule, Severity};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const SARIF_COLUMN_KIND: &str = "unicodeCodePoints";
+
+#[derive(serde::Serialize)]
+struct Log<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<Run<'a>>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Run<'a> {
+    tool: Tool<'a>,
+    column_kind: &'static str,
+    original_uri_base_ids: BTreeMap<String, ArtifactLocation>,
+    results: Vec<SarifResult<'a>>,
+}
+
+#[derive(serde::Serialize)]
+struct Tool<'a> {
+    driver: Driver<'a>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver<'a> {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<ReportingDescriptor<'a>>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportingDescriptor<'a> {
+    id: &'a str,
+    short_description: Message<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_configuration: Option<Configuration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    properties: Option<Properties<'a>>,
+}
+
+#[derive(serde::Serialize)]
+struct Configuration {
+    level: &'static str,
+}
+
+#[derive(serde::Serialize)]
+struct Properties<'a> {
+    tags: Vec<&'a str>,
+}
+
+#[derive(serde::Serialize)]
+struct Message<'a> {
+    text: &'a str,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult<'a> {
+    rule_id: &'a str,
+    rule_index: usize,
+    message: Message<'a>,
+    locations: Vec<Location>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<Region>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactLocation {
+    uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    uri_base_id: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_column: Option<usize>,
+}
+
+/// Convert byte offset in the line into 1-based column counted in code points
+///
+/// Only the part of the line kept in span text can be converted precisely,
+/// characters past it are counted as single byte ones
+fn get_column(span: &LineSpan, offset: usize) -> usize {
+    let known_length = offset.min(span.text.len());
+    let known_chars = span
+        .text
+        .get(..known_length)
+        .map_or(known_length, |known| known.chars().count());
+    known_chars + (offset - known_length) + 1
+}
+
+/// Get unique ids of the rules, indexed by rule number
+///
+/// Rule id is used, or title for rules without id. As neither is
+/// guaranteed to be unique, rule number is appended to ids shared by
+/// multiple rules, so consumers do not merge unrelated rules
+fn get_rule_ids(rules: &[Rule]) -> Vec<String> {
+    let get_base_id = |rule: &Rule| rule.id.clone().unwrap_or_else(|| rule.title.clone());
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for rule in rules {
+        *counts.entry(get_base_id(rule)).or_default() += 1;
+    }
+
+    rules
+        .iter()
+        .map(|rule| {
+            let id = get_base_id(rule);
+            if counts[&id] > 1 {
+                format!("{}#{}", id, rule.number)
+            } else {
+                id
+            }
+        })
+        .collect()
+}
+
+fn get_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Minor => "note",
+    }
+}
+
+/// Convert path to URI reference, percent-encoding everything but unreserved characters
+fn path_to_uri(path: &Path) -> String {
+    let mut res = String::new();
+    for byte in path.to_string_lossy().replace('\\', "/").bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            res.push(byte as char);
+        } else {
+            res.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    res
+}
+
+fn root_to_uri(root: &Path) -> String {
+    let mut uri = path_to_uri(&std::path::absolute(root).unwrap_or_else(|_| root.to_path_buf()));
+    if !uri.starts_with('/') {
+        uri.insert(0, '/');
+    }
+    if !uri.ends_with('/') {
+        uri.push('/');
+    }
+    format!("file://{uri}")
+}
+
+fn convert_rule<'a>(rule: &'a Rule, rule_ids: &'a [String]) -> ReportingDescriptor<'a> {
+    let mut tags: Vec<&str> = rule.tags.iter().map(|tag| tag.as_str()).collect();
+    tags.sort();
+    ReportingDescriptor {
+        id: &rule_ids[rule.number],
+        short_description: Message { text: &rule.title },
+        default_configuration: rule.get_severity().map(|severity| Configuration {
+            level: get_level(severity),
+        }),
+        properties: (!tags.is_empty()).then_some(Properties { tags }),
+    }
+}
+
+fn convert_match<'a>(
+    m: &'a Match,
+    rule_ids: &'a [String],
+    root_base_ids: &BTreeMap<&Path, String>,
+) -> SarifResult<'a> {
+    let file = m.file.as_ref();
+    SarifResult {
+        rule_id: &rule_ids[m.rule.number],
+        rule_index: m.rule.number,
+        message: Message {
+            text: &m.rule.title,
+        },
+        locations: vec![Location {
+            physical_location: PhysicalLocation {
+                artifact_location: ArtifactLocation {
+                    // root-level matches refer to the root directory itself
+                    uri: file.map_or_else(|| "./".to_owned(), |file| path_to_uri(&file.path)),
+                    uri_base_id: Some(root_base_ids[m.root].clone()),
+                },
+                region: file.and_then(|file| {
+                    let span = file.span.as_ref();
+                    file.line.map(|line| Region {
+                        start_line: line + 1,
+                        start_column: span.map(|span| get_column(span, span.start)),
+                        end_column: span.map(|span| get_column(span, span.end)),
+                    })
+                }),
+            },
+        }],
+    }
+}
+
+pub fn format_matches(rules: &[Rule], match_result: &MatchResult) {
+    let mut roots: Vec<&Path> = match_result.matches.iter().map(|m| m.root).collect();
+    roots.sort();
+    roots.dedup();
+
+    let root_base_ids: BTreeMap<&Path, String> = roots
+        .iter()
+        .enumerate()
+        .map(|(n, root)| (*root, format!("ROOT{n}")))
+        .collect();
+
+    let rule_ids = get_rule_ids(rules);
+
+    let mut matches: Vec<&Match> = match_result.matches.iter().collect();
+    sort_matches_by_root(&mut matches);
+
+    let run = Run {
+        tool: Tool {
+            driver: Driver {
+                name: env!("CARGO_PKG_NAME"),
+                version: env!("CARGO_PKG_VERSION"),
+                information_uri: env!("CARGO_PKG_HOMEPAGE"),
+                rules: rules
+                    .iter()
+                    .map(|rule| convert_rule(rule, &rule_ids))
+                    .collect(),
+            },
+        },
+        column_kind: SARIF_COLUMN_KIND,
+        original_uri_base_ids: root_base_ids
+            .iter()
+            .map(|(root, base_id)| {
+                let location = ArtifactLocation {
+                    uri: root_to_uri(root),
+                    uri_base_id: None,
+                };
+                (base_id.clone(), location)
+            })
+            .collect(),
+        results: matches
+            .into_iter()
+            .map(|m| convert_match(m, &rule_ids, &root_base_ids))
+            .collect(),
+    };
+
+    let log = Log {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![run],
+    };
+
+    println!("{}", serde_json::to_string_pretty(&log).unwrap());
+}
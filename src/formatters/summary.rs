@@ -0,0 +1,95 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use crate::r#match::{MatchResult, ScanStats};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+#[derive(serde::Serialize)]
+struct RuleCount<'a> {
+    rule: &'a str,
+    matches: usize,
+}
+
+#[derive(serde::Serialize)]
+struct RootCount {
+    root: String,
+    matches: usize,
+}
+
+/// Totals of a run, printed after the report with `--summary`
+#[derive(serde::Serialize)]
+pub struct Summary<'a> {
+    matches: usize,
+    rules: Vec<RuleCount<'a>>,
+    roots: Vec<RootCount>,
+    #[serde(flatten)]
+    scan_stats: ScanStats,
+    elapsed_seconds: f64,
+}
+
+impl<'a> Summary<'a> {
+    pub fn new(match_result: &'a MatchResult, elapsed: Duration) -> Self {
+        let mut rule_counts: BTreeMap<usize, RuleCount> = BTreeMap::new();
+        let mut root_counts: BTreeMap<&Path, usize> = BTreeMap::new();
+        for m in &match_result.matches {
+            rule_counts
+                .entry(m.rule.number)
+                .or_insert(RuleCount {
+                    rule: &m.rule.title,
+                    matches: 0,
+                })
+                .matches += 1;
+            *root_counts.entry(m.root).or_default() += 1;
+        }
+
+        Self {
+            matches: match_result.matches.len(),
+            rules: rule_counts.into_values().collect(),
+            roots: root_counts
+                .into_iter()
+                .map(|(root, matches)| RootCount {
+                    root: root.display().to_string(),
+                    matches,
+                })
+                .collect(),
+            scan_stats: match_result.stats,
+            elapsed_seconds: elapsed.as_secs_f64(),
+        }
+    }
+
+    pub fn format_text(&self) -> String {
+        let mut output = String::new();
+
+        if !self.rules.is_empty() {
+            writeln!(output, "Matches per rule:").unwrap();
+            for rule_count in &self.rules {
+                writeln!(output, "  {}: {}", rule_count.rule, rule_count.matches).unwrap();
+            }
+            writeln!(output, "Matches per root:").unwrap();
+            for root_count in &self.roots {
+                writeln!(output, "  {}: {}", root_count.root, root_count.matches).unwrap();
+            }
+        }
+
+        let stats = &self.scan_stats;
+        writeln!(
+            output,
+            "Total: {} match(es) in {} of {} root(s)",
+            self.matches,
+            self.roots.len(),
+            stats.roots_scanned
+        )
+        .unwrap();
+        writeln!(
+            output,
+            "Walked {} file(s), read {} file(s) ({} byte(s)) in {:.2}s",
+            stats.files_walked, stats.files_read, stats.bytes_read, self.elapsed_seconds
+        )
+        .unwrap();
+
+        output
+    }
+}
@@ -1,7 +1,9 @@
 // SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
-use crate::r#match::{Match, MatchResult};
+use crate::formatters::{order_by_rules, sort_matches_by_root};
+use crate::r#match::{LineSpan, Match, MatchResult};
+use crate::ruleset::Severity;
 use colored::*;
 
 #[allow(clippy::enum_variant_names)]
@@ -29,27 +31,11 @@ pub enum Palette {
     TrueColor,
 }
 
-fn order_by_roots(lhs: &&Match, rhs: &&Match) -> std::cmp::Ordering {
-    lhs.root
-        .cmp(rhs.root)
-        .then_with(|| lhs.file.cmp(&rhs.file)) // note: sorts by both path and line
-        .then_with(|| lhs.rule.number.cmp(&rhs.rule.number))
-}
-
-fn order_by_rules(lhs: &&Match, rhs: &&Match) -> std::cmp::Ordering {
-    lhs.rule
-        .number
-        .cmp(&rhs.rule.number)
-        .then_with(|| lhs.root.cmp(rhs.root))
-        .then_with(|| lhs.file.cmp(&rhs.file)) // note: sorts by both path and line
-        .then_with(|| lhs.rule.number.cmp(&rhs.rule.number))
-}
-
 fn sort_matches(matches: &mut [&Match], format: Format) {
     match format {
         Format::ByRule => matches.sort_unstable_by(order_by_rules),
         Format::ByRootGrouped | Format::ByRootFullPaths | Format::ByPath => {
-            matches.sort_unstable_by(order_by_roots)
+            sort_matches_by_root(matches)
         }
     }
 }
@@ -83,18 +69,12 @@ fn get_title(m: &Match, palette: Palette) -> String {
             _ => unreachable!(),
         }
         .to_string(),
-        Palette::Severity => {
-            let tags = &m.rule.tags;
-            if tags.contains("error") || tags.contains("fatal") || tags.contains("critical") {
-                title.red().to_string()
-            } else if tags.contains("warning") {
-                title.yellow().to_string()
-            } else if tags.contains("minor") {
-                title
-            } else {
-                title.green().to_string()
-            }
-        }
+        Palette::Severity => match m.rule.get_severity() {
+            Some(Severity::Error) => title.red().to_string(),
+            Some(Severity::Warning) => title.yellow().to_string(),
+            Some(Severity::Minor) => title,
+            None => title.green().to_string(),
+        },
         Palette::TrueColor => {
             let hash = fxhash::hash32(&m.rule.title);
             let r = hash >> 4 & 0b1111;
@@ -141,14 +121,82 @@ fn get_path(m: &Match, format: Format) -> Option<String> {
     }
 }
 
+fn get_column(m: &Match) -> Option<usize> {
+    m.file
+        .as_ref()
+        .and_then(|file| file.span.as_ref())
+        .map(|span| span.start)
+}
+
 fn get_location(m: &Match, format: Format) -> Option<String> {
-    match (get_path(m, format), get_line(m)) {
-        (Some(path), Some(line)) => Some(format!("{}{}{}", path.bold(), ":".cyan(), line + 1)),
-        (Some(path), None) => Some(format!("{}", path.bold())),
-        (None, Some(line)) => Some(format!("line {}", line + 1)),
-        (None, None) => None, //Some("general".into()),
-                              //(None, None) => Some("???".into()),
+    match (get_path(m, format), get_line(m), get_column(m)) {
+        (Some(path), Some(line), Some(column)) => Some(format!(
+            "{}{}{}{}{}",
+            path.bold(),
+            ":".cyan(),
+            line + 1,
+            ":".cyan(),
+            column + 1
+        )),
+        (Some(path), Some(line), None) => {
+            Some(format!("{}{}{}", path.bold(), ":".cyan(), line + 1))
+        }
+        (Some(path), None, _) => Some(format!("{}", path.bold())),
+        (None, Some(line), Some(column)) => {
+            Some(format!("line {}, column {}", line + 1, column + 1))
+        }
+        (None, Some(line), None) => Some(format!("line {}", line + 1)),
+        (None, None, _) => None, //Some("general".into()),
+                                 //(None, None) => Some("???".into()),
+    }
+}
+
+fn highlight_span(span: &LineSpan) -> String {
+    // span may point past the text if it was truncated
+    let end = span.end.min(span.text.len());
+    let start = span.start.min(end);
+    format!(
+        "{}{}{}",
+        &span.text[..start],
+        span.text[start..end].red().bold(),
+        &span.text[end..]
+    )
+}
+
+/// Format lines surrounding the matched line, with line numbers and
+/// separators in the style of grep (`:` for matched line, `-` for context)
+fn get_context_lines(m: &Match, format: Format) -> Vec<String> {
+    let Some(file) = &m.file else {
+        return vec![];
+    };
+    let (Some(line), Some(span), Some(context)) = (file.line, &file.span, &file.context) else {
+        return vec![];
+    };
+
+    let first_line = line - context.before.len() as u64;
+    let last_line = line + context.after.len() as u64;
+    let width = (last_line + 1).to_string().len();
+    let prefix = format!("{}  ", get_match_prefix(format));
+
+    let format_line = |line_number: u64, separator: &str, text: &str| {
+        format!(
+            "{}{:>width$}{} {}",
+            prefix,
+            line_number + 1,
+            separator.cyan(),
+            text
+        )
+    };
+
+    let mut res = vec![];
+    for (offset, text) in context.before.iter().enumerate() {
+        res.push(format_line(first_line + offset as u64, "-", text));
+    }
+    res.push(format_line(line, ":", &highlight_span(span)));
+    for (offset, text) in context.after.iter().enumerate() {
+        res.push(format_line(line + 1 + offset as u64, "-", text));
     }
+    res
 }
 
 fn get_group_prefix(_format: Format) -> &'static str {
@@ -184,5 +232,9 @@ pub fn format_matches(match_result: &MatchResult, format: Format, palette: Palet
             (None, Some(m)) => println!("{}{}", get_match_prefix(format), m),
             (None, None) => {}
         }
+
+        for line in get_context_lines(m, format) {
+            println!("{line}");
+        }
     }
 }
@@ -2,22 +2,41 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
 mod applier;
+mod baseline;
 mod config;
+mod explanation;
+mod fingerprint;
+mod fixer;
 mod formatters;
 mod r#match;
 mod ruleset;
 
-use crate::applier::apply_ruleset;
+use crate::applier::{ContextLines, apply_ruleset, warn_about_unused_ignore_entries};
+use crate::baseline::{Baseline, write_baseline};
 use crate::config::Config;
+use crate::fixer::apply_fixes;
 use crate::format_text::Palette;
+use crate::formatters::checkstyle as format_checkstyle;
+use crate::formatters::csv as format_csv;
+use crate::formatters::explain as format_explain;
+use crate::formatters::github as format_github;
+use crate::formatters::gitlab as format_gitlab;
 use crate::formatters::json as format_json;
+use crate::formatters::junit as format_junit;
+use crate::formatters::matrix as format_matrix;
+use crate::formatters::report as format_report;
+use crate::formatters::sarif as format_sarif;
+use crate::formatters::summary::Summary;
 use crate::formatters::text as format_text;
 use crate::r#match::MatchResult;
+use crate::ruleset::compile::CompiledRuleset;
 use anyhow::{Error, bail};
 use clap::{Parser, ValueEnum};
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
+use std::sync::Mutex;
+use std::time::Instant;
 
 const CONFIG_FILE_NAME: &str = "omnilinter.conf";
 
@@ -37,6 +56,39 @@ enum OutputFormat {
 
     /// JSON output
     Json,
+
+    /// JSON Lines output, streamed as soon as each root is processed
+    Jsonl,
+
+    /// SARIF 2.1.0 output, for code scanning tools
+    Sarif,
+
+    /// Checkstyle XML output
+    Checkstyle,
+
+    /// JUnit XML output, with a test case per rule
+    Junit,
+
+    /// GitHub Actions workflow commands, for inline annotations
+    Github,
+
+    /// GitLab Code Quality report
+    Gitlab,
+
+    /// Comma separated values, with a header row
+    Csv,
+
+    /// Tab separated values, with a header row
+    Tsv,
+
+    /// Markdown report with summary tables and collapsible sections per root
+    Markdown,
+
+    /// Standalone HTML report with summary tables and collapsible sections per root
+    Html,
+
+    /// Table of match counts per root and rule (CSV when not printing to terminal)
+    Matrix,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -82,11 +134,51 @@ struct Args {
     #[arg(long, value_name = "EXITCODE")]
     error_exitcode: Option<u8>,
 
+    /// Report config errors, but continue with successfully parsed rules
+    #[arg(long = "keep-going")]
+    keep_going: bool,
+
+    /// Instead of reporting matches, explain how rule with given id or title was evaluated
+    #[arg(long = "explain", value_name = "RULE_ID_OR_TITLE")]
+    explained_rule: Option<String>,
+
+    /// Record all current matches into given baseline file instead of reporting them
+    #[arg(long = "write-baseline", value_name = "BASELINE_PATH", conflicts_with_all = ["baseline_path", "explained_rule"])]
+    write_baseline_path: Option<PathBuf>,
+
+    /// Do not report matches recorded in given baseline file
+    #[arg(long = "baseline", value_name = "BASELINE_PATH")]
+    baseline_path: Option<PathBuf>,
+
+    /// Fix matched lines using replacements specified in rules
+    #[arg(long = "fix", conflicts_with_all = ["write_baseline_path", "explained_rule"])]
+    fix: bool,
+
+    /// With --fix, print unified diff of changes instead of modifying files
+    #[arg(long = "dry-run", requires = "fix")]
+    dry_run: bool,
+
+    /// Print NUM lines of context after matched lines (text formats only)
+    #[arg(short = 'A', long = "after-context", value_name = "NUM")]
+    after_context: Option<usize>,
+
+    /// Print NUM lines of context before matched lines (text formats only)
+    #[arg(short = 'B', long = "before-context", value_name = "NUM")]
+    before_context: Option<usize>,
+
+    /// Print NUM lines of context around matched lines (text formats only)
+    #[arg(short = 'C', long = "context", value_name = "NUM")]
+    context: Option<usize>,
+
+    /// Print summary with match counts and amounts of processed data after the report
+    #[arg(long = "summary")]
+    summary: bool,
+
     /// Number of target directories to process simultaneously
     #[arg(short = 'j', long = "jobs", value_name = "JOBS")]
     num_threads: Option<usize>,
 
-    /// Parse and dump specified config, do nothing else
+    /// Parse and dump specified config, do nothing else (use `--format json` for JSON dump)
     #[arg(long = "dump-config", value_name = "CONFIG_PATH", hide = true)]
     config_to_dump: Option<PathBuf>,
 
@@ -113,28 +205,74 @@ fn get_default_config_path() -> Option<PathBuf> {
 }
 
 fn read_config(args: &Args) -> Result<Config, Error> {
-    if !args.config_paths.is_empty() {
+    let mut errors = vec![];
+
+    let config = if !args.config_paths.is_empty() {
         let mut config = Config::new();
         for path in &args.config_paths {
-            config.merge_from(Config::from_file_expand_includes(path)?);
+            config.merge_from(Config::from_file_expand_includes_collect_errors(
+                path,
+                &mut errors,
+            ));
         }
-        Ok(config)
+        config
     } else if let Some(path) = get_default_config_path() {
-        Ok(Config::from_file_expand_includes(&path)?)
+        Config::from_file_expand_includes_collect_errors(&path, &mut errors)
     } else {
         bail!(
             "config file is neither specified on the command line, nor present in the application config directory"
         );
+    };
+
+    if errors.is_empty() {
+        return Ok(config);
+    }
+
+    for error in &errors {
+        eprintln!("Error: {error:?}\n");
+    }
+
+    if !args.keep_going {
+        bail!("{} error(s) found in config", errors.len());
+    }
+
+    eprintln!(
+        "Warning: {} error(s) found in config, continuing with {} successfully parsed rule(s)",
+        errors.len(),
+        config.ruleset.rules.len()
+    );
+    Ok(config)
+}
+
+/// Check a single root, and do the processing of its matches which
+/// does not require other roots to be checked
+fn process_root<'a>(
+    ruleset: &'a CompiledRuleset,
+    root: &'a Path,
+    context_lines: ContextLines,
+    baseline: Option<&Mutex<Baseline>>,
+    is_streaming: bool,
+) -> MatchResult<'a> {
+    let mut result = apply_ruleset(ruleset, root, context_lines);
+    if let Some(baseline) = baseline {
+        baseline.lock().unwrap().apply(&mut result);
     }
+    if is_streaming {
+        format_json::format_matches_as_lines(&result);
+    }
+    result
 }
 
 fn main() -> Result<ExitCode, Error> {
     let args = Args::parse();
 
     if let Some(config_to_dump) = args.config_to_dump {
-        Config::from_file_expand_includes(&config_to_dump)
-            .unwrap()
-            .dump();
+        let config = Config::from_file_expand_includes(&config_to_dump).unwrap();
+        if args.output_format == OutputFormat::Json {
+            config.dump_json();
+        } else {
+            config.dump();
+        }
         return Ok(ExitCode::SUCCESS);
     }
 
@@ -171,13 +309,46 @@ fn main() -> Result<ExitCode, Error> {
         &HashSet::from_iter(args.ignored_tags.iter().map(|tag| tag.to_lowercase())),
     );
 
+    if let Some(explained_rule) = &args.explained_rule
+        && config.ruleset.mark_explained(explained_rule) == 0
+    {
+        bail!("no rule with id or title \"{explained_rule}\" found in the ruleset");
+    }
+
     let ruleset = config.ruleset.compile();
 
-    let result = {
+    let baseline = args
+        .baseline_path
+        .as_deref()
+        .map(Baseline::load)
+        .transpose()?
+        .map(Mutex::new);
+
+    // fixes are only known after all roots are processed
+    let is_streaming = args.output_format == OutputFormat::Jsonl
+        && !args.fix
+        && args.explained_rule.is_none()
+        && args.write_baseline_path.is_none();
+
+    // context lines are only printed by text formats
+    let context_lines = match args.output_format {
+        OutputFormat::ByRoot
+        | OutputFormat::FullPaths
+        | OutputFormat::ByRule
+        | OutputFormat::ByPath => ContextLines {
+            before: args.before_context.or(args.context).unwrap_or(0),
+            after: args.after_context.or(args.context).unwrap_or(0),
+        },
+        _ => Default::default(),
+    };
+
+    let start_time = Instant::now();
+
+    let mut result = {
         #[cfg(feature = "multithreading")]
         {
             use rayon::prelude::*;
-            use std::sync::{Arc, Mutex};
+            use std::sync::Arc;
 
             if let Some(num_threads) = args.num_threads {
                 rayon::ThreadPoolBuilder::new()
@@ -189,7 +360,13 @@ fn main() -> Result<ExitCode, Error> {
             let shared_result = Arc::new(Mutex::new(MatchResult::new()));
 
             roots.par_iter().for_each(|root| {
-                let partial_result = apply_ruleset(&ruleset, root);
+                let partial_result = process_root(
+                    &ruleset,
+                    root,
+                    context_lines,
+                    baseline.as_ref(),
+                    is_streaming,
+                );
                 shared_result.lock().unwrap().append(partial_result);
             });
 
@@ -201,14 +378,48 @@ fn main() -> Result<ExitCode, Error> {
         #[cfg(not(feature = "multithreading"))]
         {
             let mut result = MatchResult::new();
-            roots
-                .iter()
-                .for_each(|root| result.append(apply_ruleset(&ruleset, root)));
+            roots.iter().for_each(|root| {
+                result.append(process_root(
+                    &ruleset,
+                    root,
+                    context_lines,
+                    baseline.as_ref(),
+                    is_streaming,
+                ))
+            });
 
             result
         }
     };
 
+    warn_about_unused_ignore_entries(&mut result.unused_ignore_entries);
+
+    if args.explained_rule.is_some() {
+        format_explain::format_explanations(&result);
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if let Some(write_baseline_path) = &args.write_baseline_path {
+        write_baseline(&result, write_baseline_path)?;
+        eprintln!(
+            "{} match(es) written to baseline {}",
+            result.matches.len(),
+            write_baseline_path.display()
+        );
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if args.fix {
+        apply_fixes(&mut result, args.dry_run);
+        if args.dry_run {
+            return Ok(ExitCode::SUCCESS);
+        }
+    }
+
+    let summary = args
+        .summary
+        .then(|| Summary::new(&result, start_time.elapsed()));
+
     match args.output_format {
         OutputFormat::ByRoot => {
             format_text::format_matches(&result, format_text::Format::ByRootGrouped, args.palette)
@@ -222,7 +433,41 @@ fn main() -> Result<ExitCode, Error> {
         OutputFormat::ByPath => {
             format_text::format_matches(&result, format_text::Format::ByPath, args.palette)
         }
-        OutputFormat::Json => format_json::format_matches(&result),
+        OutputFormat::Json => format_json::format_matches(&result, summary.as_ref()),
+        OutputFormat::Jsonl => {
+            if !is_streaming {
+                format_json::format_matches_as_lines(&result)
+            }
+        }
+        OutputFormat::Sarif => format_sarif::format_matches(&ruleset.rules, &result),
+        OutputFormat::Checkstyle => format_checkstyle::format_matches(&result),
+        OutputFormat::Junit => format_junit::format_matches(&ruleset.rules, &roots, &result),
+        OutputFormat::Github => format_github::format_matches(&result),
+        OutputFormat::Gitlab => format_gitlab::format_matches(&result),
+        OutputFormat::Csv => format_csv::format_matches(&result, format_csv::Format::Csv),
+        OutputFormat::Tsv => format_csv::format_matches(&result, format_csv::Format::Tsv),
+        OutputFormat::Markdown => {
+            format_report::format_matches(&result, format_report::Format::Markdown)
+        }
+        OutputFormat::Html => format_report::format_matches(&result, format_report::Format::Html),
+        OutputFormat::Matrix => format_matrix::format_matches(&roots, &result),
+    }
+
+    if let Some(summary) = &summary {
+        match args.output_format {
+            OutputFormat::ByRoot
+            | OutputFormat::FullPaths
+            | OutputFormat::ByRule
+            | OutputFormat::ByPath => {
+                if !result.is_empty() {
+                    println!();
+                }
+                print!("{}", summary.format_text());
+            }
+            OutputFormat::Json => {}
+            // do not break machine readable output
+            _ => eprint!("{}", summary.format_text()),
+        }
     }
 
     if let Some(error_exitcode) = args.error_exitcode
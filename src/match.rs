@@ -1,22 +1,58 @@
 // SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+use crate::applier::UnusedIgnoreEntry;
+use crate::explanation::Explanation;
+use crate::fingerprint::fnv1a;
 use crate::ruleset::Rule;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
+/// Counters of work done while checking roots
+#[derive(Default, Clone, Copy, serde::Serialize)]
+#[cfg_attr(not(feature = "coverage"), derive(Debug))]
+pub struct ScanStats {
+    pub roots_scanned: usize,
+    pub files_walked: usize,
+    pub files_read: usize,
+    pub bytes_read: u64,
+}
+
+impl ScanStats {
+    pub fn add(&mut self, other: &Self) {
+        self.roots_scanned += other.roots_scanned;
+        self.files_walked += other.files_walked;
+        self.files_read += other.files_read;
+        self.bytes_read += other.bytes_read;
+    }
+}
+
 #[derive(Default)]
 pub struct MatchResult<'a> {
     pub matches: Vec<Match<'a>>,
+    pub explanations: Vec<Explanation<'a>>,
+    pub stats: ScanStats,
+    /// Reported once all roots are processed
+    pub unused_ignore_entries: Vec<UnusedIgnoreEntry>,
 }
 
 impl<'a> MatchResult<'a> {
     pub fn new() -> Self {
-        Self { matches: vec![] }
+        Self {
+            matches: vec![],
+            explanations: vec![],
+            stats: Default::default(),
+            unused_ignore_entries: vec![],
+        }
     }
 
     pub fn append(&mut self, mut other: Self) {
         self.matches.append(&mut other.matches);
+        self.explanations.append(&mut other.explanations);
+        self.stats.add(&other.stats);
+        self.unused_ignore_entries
+            .append(&mut other.unused_ignore_entries);
     }
 
     pub fn is_empty(&self) -> bool {
@@ -35,10 +71,60 @@ pub struct Match<'a> {
     pub file: Option<FileMatch>,
 }
 
+/// Longer matched lines are truncated, as these are likely minified or generated
+const MAX_LINE_TEXT_LENGTH: usize = 512;
+
 #[derive(PartialEq, PartialOrd, Eq, Ord)]
 pub struct FileMatch {
     pub path: Rc<PathBuf>,
     pub line: Option<u64>,
+    /// Matched part of the line, only present along with the line
+    pub span: Option<LineSpan>,
+    /// Surrounding lines, only present if requested
+    pub context: Option<LineContext>,
+}
+
+/// Truncate line text to `MAX_LINE_TEXT_LENGTH` bytes
+pub fn truncate_line(line: &str) -> &str {
+    let mut length = line.len().min(MAX_LINE_TEXT_LENGTH);
+    while !line.is_char_boundary(length) {
+        length -= 1;
+    }
+    &line[..length]
+}
+
+/// Part of the line matched by a regex, along with the line text
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone)]
+#[cfg_attr(not(feature = "coverage"), derive(Debug))]
+pub struct LineSpan {
+    /// Zero-based byte offset of the start of the matched part
+    pub start: usize,
+    /// Zero-based byte offset past the end of the matched part
+    pub end: usize,
+    /// Text of the line, truncated to `MAX_LINE_TEXT_LENGTH` bytes
+    pub text: String,
+    /// Hash of the whole line with surrounding whitespace trimmed,
+    /// captured during the scan so the file is not read again
+    pub fingerprint: u64,
+}
+
+impl LineSpan {
+    pub fn new(line: &str, range: Range<usize>) -> Self {
+        Self {
+            start: range.start,
+            end: range.end,
+            text: truncate_line(line).to_owned(),
+            fingerprint: fnv1a(line.trim().as_bytes()),
+        }
+    }
+}
+
+/// Lines preceding and following the matched line, truncated the same way as its text
+#[derive(PartialEq, PartialOrd, Eq, Ord, Clone, Default)]
+#[cfg_attr(not(feature = "coverage"), derive(Debug))]
+pub struct LineContext {
+    pub before: Vec<String>,
+    pub after: Vec<String>,
 }
 
 impl<'a> Match<'a> {
@@ -54,17 +140,31 @@ impl<'a> Match<'a> {
         Match {
             rule,
             root,
-            file: Some(FileMatch { path, line: None }),
+            file: Some(FileMatch {
+                path,
+                line: None,
+                span: None,
+                context: None,
+            }),
         }
     }
 
-    pub fn for_line(rule: &'a Rule, root: &'a Path, path: Rc<PathBuf>, line: u64) -> Match<'a> {
+    pub fn for_line(
+        rule: &'a Rule,
+        root: &'a Path,
+        path: Rc<PathBuf>,
+        line: u64,
+        span: LineSpan,
+        context: Option<LineContext>,
+    ) -> Match<'a> {
         Match {
             rule,
             root,
             file: Some(FileMatch {
                 path,
                 line: Some(line),
+                span: Some(span),
+                context,
             }),
         }
     }
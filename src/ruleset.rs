@@ -26,4 +26,29 @@ impl Ruleset {
                 && (required_tags.is_empty() || !rule.tags.is_disjoint(required_tags))
         })
     }
+
+    /// Mark rules for explanation, returning number of marked rules
+    ///
+    /// Rule is looked up by id first, as these are meant to identify
+    /// a single rule, falling back to titles, which may be shared
+    pub fn mark_explained(&mut self, id_or_title: &str) -> usize {
+        let id = id_or_title.to_lowercase();
+        let has_id_match = self.rules.iter().any(|rule| rule.id.as_ref() == Some(&id));
+
+        let mut count = 0;
+        self.rules
+            .iter_mut()
+            .filter(|rule| {
+                if has_id_match {
+                    rule.id.as_ref() == Some(&id)
+                } else {
+                    rule.title == id_or_title
+                }
+            })
+            .for_each(|rule| {
+                rule.is_explained = true;
+                count += 1;
+            });
+        count
+    }
 }
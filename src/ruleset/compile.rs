@@ -39,6 +39,9 @@ fn enumerate_regex_condition(condition: &mut RegexCondition, enumerator: &mut En
         .excludes
         .iter_mut()
         .for_each(|regex| regex.enumerate_with(enumerator));
+    if let Some(follow_up) = &mut condition.follow_up {
+        enumerate_regex_condition(&mut follow_up.condition, enumerator);
+    }
 }
 
 /// Whether this content condition requires file lines to be processed
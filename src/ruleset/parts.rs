@@ -8,9 +8,10 @@ pub mod regex;
 pub mod rule;
 
 pub use content_conditions::{
-    ContentCondition, ContentConditionNode, RegexCondition, SizeCondition, SizeOperator,
+    ContentCondition, ContentConditionNode, FollowUpCondition, LineRange, RegexCondition,
+    SizeCondition, SizeOperator,
 };
 pub use glob::Glob;
 pub use path_conditions::{ConditionLogic, GlobCondition};
 pub use regex::Regex;
-pub use rule::Rule;
+pub use rule::{Rule, Severity};
@@ -2,12 +2,105 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
 use super::regex::Regex;
+use std::ops::Range;
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum LineRange {
+    /// First given number of lines of a file
+    First(u64),
+    /// Last given number of lines of a file
+    Last(u64),
+    /// Inclusive range of 1-based line numbers
+    Span(u64, u64),
+}
+
+impl LineRange {
+    /// Whether the range can only be resolved when total number of lines is known
+    pub fn is_relative_to_end(&self) -> bool {
+        matches!(self, LineRange::Last(_))
+    }
+
+    /// Whether zero-based line number falls into the range in a file with given number of lines
+    pub fn contains(&self, line_number: u64, num_lines: u64) -> bool {
+        match *self {
+            LineRange::First(count) => line_number < count,
+            LineRange::Last(count) => line_number + count >= num_lines,
+            LineRange::Span(first, last) => line_number + 1 >= first && line_number < last,
+        }
+    }
+
+    /// Whether none of the lines following given number of already read lines fall into the range
+    pub fn is_over(&self, num_lines_read: u64) -> bool {
+        match *self {
+            LineRange::First(count) => num_lines_read >= count,
+            LineRange::Last(_) => false,
+            LineRange::Span(_, last) => num_lines_read >= last,
+        }
+    }
+}
 
 #[derive(Default, Clone)]
 #[cfg_attr(not(feature = "coverage"), derive(Debug))]
 pub struct RegexCondition {
     pub patterns: Vec<Regex>,
     pub excludes: Vec<Regex>,
+    pub line_range: Option<LineRange>,
+    /// Required number of matched lines, any number if not specified
+    pub count: Option<SizeCondition>,
+    /// Patterns which must follow the matched line to complete the match
+    pub follow_up: Option<Box<FollowUpCondition>>,
+    /// Replacement template used to fix matched lines, may reference capture groups
+    pub replacement: Option<String>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(not(feature = "coverage"), derive(Debug))]
+pub struct FollowUpCondition {
+    /// Only patterns and excludes are used here
+    pub condition: RegexCondition,
+    /// Maximal distance in lines from the line matched by preceding condition
+    pub max_distance: Option<u64>,
+}
+
+impl RegexCondition {
+    /// Whether matches can only be resolved after the whole file is read
+    pub fn is_deferred(&self) -> bool {
+        self.line_range
+            .as_ref()
+            .is_some_and(LineRange::is_relative_to_end)
+    }
+
+    /// Condition which matches the reported line
+    ///
+    /// For conditions with follow-up patterns, the line which completes
+    /// the sequence is reported, so it is matched by follow-up patterns.
+    pub fn get_reported_line_condition(&self) -> &RegexCondition {
+        self.follow_up
+            .as_ref()
+            .map_or(self, |follow_up| &follow_up.condition)
+    }
+
+    /// Byte range of the part of the reported line matched by the condition
+    pub fn find_span(&self, line: &str) -> Option<Range<usize>> {
+        self.get_reported_line_condition()
+            .patterns
+            .iter()
+            .find_map(|regex| regex.find(line))
+    }
+
+    /// Whether the condition applies to given zero-based line number
+    pub fn applies_to_line(&self, line_number: u64) -> bool {
+        self.line_range
+            .as_ref()
+            .is_none_or(|line_range| line_range.contains(line_number, line_number + 1))
+    }
+
+    /// Whether the condition does not apply to any lines after given number of already read lines
+    pub fn is_exhausted(&self, num_lines_read: u64) -> bool {
+        self.line_range
+            .as_ref()
+            .is_some_and(|line_range| line_range.is_over(num_lines_read))
+    }
 }
 
 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -18,6 +111,8 @@ pub enum SizeOperator {
     LessEqual,
     Equal,
     NotEqual,
+    /// Inclusive range from the condition value to the given upper bound
+    Range(u64),
 }
 
 #[derive(Clone)]
@@ -36,6 +131,7 @@ impl SizeCondition {
             SizeOperator::Less => value < self.value,
             SizeOperator::Equal => value == self.value,
             SizeOperator::NotEqual => value != self.value,
+            SizeOperator::Range(upper_value) => value >= self.value && value <= upper_value,
         }
     }
 
@@ -47,6 +143,7 @@ impl SizeCondition {
             SizeOperator::Less => false,
             SizeOperator::Equal => false,
             SizeOperator::NotEqual => value > self.value,
+            SizeOperator::Range(_) => false,
         }
     }
 }
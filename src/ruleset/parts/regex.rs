@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: GPL-3.0-or-later
 
 use crate::ruleset::enumerator::Enumerator;
+use std::borrow::Cow;
+use std::ops::Range;
 
 #[derive(Clone)]
 #[cfg_attr(not(feature = "coverage"), derive(Debug))]
@@ -22,6 +24,15 @@ impl Regex {
         self.regex.is_match(haystack)
     }
 
+    /// Byte range of the leftmost match in the haystack
+    pub fn find(&self, haystack: &str) -> Option<Range<usize>> {
+        self.regex.find(haystack).map(|m| m.range())
+    }
+
+    pub fn replace_all<'h>(&self, haystack: &'h str, replacement: &str) -> Cow<'h, str> {
+        self.regex.replace_all(haystack, replacement)
+    }
+
     pub fn as_str(&self) -> &str {
         self.regex.as_str()
     }
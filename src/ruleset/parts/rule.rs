@@ -9,9 +9,20 @@ use std::collections::HashSet;
 pub struct Rule {
     pub number: usize,
     pub title: String,
+    pub id: Option<String>,
     pub tags: HashSet<String>,
     pub path_conditions: Vec<GlobCondition>,
     pub is_reporting_target: bool,
+    pub is_explained: bool,
+}
+
+/// Severity of a rule, guessed from its tags
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(not(feature = "coverage"), derive(Debug))]
+pub enum Severity {
+    Minor,
+    Warning,
+    Error,
 }
 
 fn prepend_to_vec<T: Clone>(target: &mut Vec<T>, source: Vec<T>) {
@@ -28,6 +39,20 @@ impl Rule {
         prepend_to_vec(&mut self.path_conditions, template.path_conditions.clone());
     }
 
+    /// Guess severity from rule tags (error/fatal/critical; warning; minor)
+    pub fn get_severity(&self) -> Option<Severity> {
+        let tags = &self.tags;
+        if tags.contains("error") || tags.contains("fatal") || tags.contains("critical") {
+            Some(Severity::Error)
+        } else if tags.contains("warning") {
+            Some(Severity::Warning)
+        } else if tags.contains("minor") {
+            Some(Severity::Minor)
+        } else {
+            None
+        }
+    }
+
     pub fn are_all_positive_conditions_satisfied(&self, mask: &[bool]) -> bool {
         !self
             .path_conditions
@@ -57,3 +57,52 @@ fn with_omnilinter_config() {
 
     pretty_assertions::assert_eq!(first_dump, second_dump);
 }
+
+#[test]
+fn json_dump() {
+    let output = &Command::cargo_bin("omnilinter")
+        .unwrap()
+        .arg("--dump-config")
+        .arg("tests/config_parse_dump_test.conf")
+        .arg("--format=json")
+        .output()
+        .unwrap();
+
+    eprint!("{}", std::str::from_utf8(&output.stderr).unwrap());
+    assert!(output.status.success());
+
+    let dump: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let rule = &dump["rules"][1];
+    assert_eq!(rule["number"], 1);
+    assert_eq!(rule["title"], "rule2");
+    assert_eq!(rule["tags"], serde_json::json!(["bar2", "foo2"]));
+    assert_eq!(rule["is_reporting_target"], true);
+
+    let path_condition = &rule["path_conditions"][2];
+    assert_eq!(path_condition["logic"], "positive");
+    assert_eq!(
+        path_condition["patterns"],
+        serde_json::json!(["a23*.*", "b23*.*"])
+    );
+    assert_eq!(
+        path_condition["excludes"],
+        serde_json::json!(["*.c23", "*.d23"])
+    );
+
+    let content_condition = &path_condition["content_conditions"][0];
+    assert_eq!(content_condition["type"], "match");
+    assert_eq!(
+        content_condition["patterns"],
+        serde_json::json!(["a21", "b21"])
+    );
+    assert_eq!(
+        content_condition["excludes"],
+        serde_json::json!(["c21", "d21"])
+    );
+
+    let content_condition = &path_condition["content_conditions"][4];
+    assert_eq!(content_condition["type"], "size");
+    assert_eq!(content_condition["operator"], ">");
+    assert_eq!(content_condition["value"], 20);
+}
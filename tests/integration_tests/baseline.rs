@@ -0,0 +1,99 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use testutils::{TestCase, lines};
+
+fn write_baseline(test_case: &mut TestCase) {
+    test_case
+        .add_arg("--write-baseline=baseline.json")
+        .silence_stderr()
+        .run()
+        .assert_success()
+        .assert_stderr_contains("written to baseline");
+    test_case
+        .remove_arg("--write-baseline=baseline.json")
+        .add_arg("--baseline=baseline.json");
+}
+
+#[test]
+fn baseline_suppresses_recorded_matches() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["foo", "bar"])
+        .add_file("b.py", lines!["bar"])
+        .add_rule(lines!["files *.py", "match /foo/"])
+        .add_rule(lines!["files *.py", "nomatch /foo/"]);
+    write_baseline(&mut test_case);
+    test_case.run().assert_matches(vec![]);
+}
+
+#[test]
+fn baseline_survives_line_shifts() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["foo", "bar"])
+        .add_rule(lines!["files *.py", "match /foo|bar/"]);
+    write_baseline(&mut test_case);
+    test_case
+        .add_file("a.py", lines!["", "", "  bar", "foo"])
+        .run()
+        .assert_matches(vec![]);
+}
+
+#[test]
+fn baseline_reports_new_matches() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["foo", "bar"])
+        .add_rule(lines!["files *.py", "match /foo|bar|baz/"]);
+    write_baseline(&mut test_case);
+    test_case
+        .add_file("a.py", lines!["foo", "baz", "bar"])
+        .add_file("b.py", lines!["foo"])
+        .run()
+        .assert_matches(vec!["a.py:2", "b.py:1"]);
+}
+
+#[test]
+fn baseline_counts_duplicates() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["foo"])
+        .add_rule(lines!["files *.py", "match /foo/"]);
+    write_baseline(&mut test_case);
+    test_case
+        .add_file("a.py", lines!["foo", "foo"])
+        .run()
+        .assert_matches(vec!["a.py:2"]);
+}
+
+#[test]
+fn baseline_missing() {
+    TestCase::new_for_json_tests()
+        .add_file("a.py", lines!["foo"])
+        .add_rule(lines!["files *.py", "match /foo/"])
+        .add_arg("--baseline=baseline.json")
+        .silence_stderr()
+        .run()
+        .assert_failure()
+        .assert_stderr_contains("cannot read baseline file");
+}
+
+#[test]
+fn baseline_root_spelling() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["foo"])
+        .add_rule(lines!["files *.py", "match /foo/"]);
+    write_baseline(&mut test_case);
+    test_case
+        .remove_arg("root")
+        .add_arg("./root/")
+        .run()
+        .assert_matches(vec![]);
+    test_case
+        .remove_arg("./root/")
+        .add_arg("root/../root")
+        .run()
+        .assert_matches(vec![]);
+}
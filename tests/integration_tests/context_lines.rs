@@ -0,0 +1,152 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use testutils::{TestCase, lines, paths};
+
+#[test]
+fn context() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("-C1")
+        .add_file("a.py", lines!["1", "2", "foo", "4", "5"])
+        .add_named_rule("rule", lines!["files a.py", "match /foo/"])
+        .run()
+        .assert_stdout(lines![
+            "root",
+            "  a.py:3:1: rule",
+            "    2- 2",
+            "    3: foo",
+            "    4- 4"
+        ]);
+}
+
+#[test]
+fn before_and_after() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("-B2")
+        .add_arg("-A0")
+        .add_file("a.py", lines!["1", "2", "3", "foo"])
+        .add_named_rule("rule", lines!["files a.py", "match /foo/"])
+        .run()
+        .assert_stdout(lines![
+            "root",
+            "  a.py:4:1: rule",
+            "    2- 2",
+            "    3- 3",
+            "    4: foo"
+        ]);
+}
+
+#[test]
+fn overridden_context() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("-C2")
+        .add_arg("--after-context=0")
+        .add_file("a.py", lines!["1", "foo", "3"])
+        .add_named_rule("rule", lines!["files a.py", "match /foo/"])
+        .run()
+        .assert_stdout(lines!["root", "  a.py:2:1: rule", "    1- 1", "    2: foo"]);
+}
+
+#[test]
+fn file_boundaries() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("-C5")
+        .add_file("a.py", lines!["foo", "bar"])
+        .add_named_rule("rule", lines!["files a.py", "match /foo|bar/"])
+        .run()
+        .assert_stdout(lines![
+            "root",
+            "  a.py:1:1: rule",
+            "    1: foo",
+            "    2- bar",
+            "  a.py:2:1: rule",
+            "    1- foo",
+            "    2: bar"
+        ]);
+}
+
+#[test]
+fn number_alignment() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("-A1")
+        .add_file(
+            "a.py",
+            lines!["1", "2", "3", "4", "5", "6", "7", "8", "foo", "10"],
+        )
+        .add_named_rule("rule", lines!["files a.py", "match /foo/"])
+        .run()
+        .assert_stdout(lines![
+            "root",
+            "  a.py:9:1: rule",
+            "     9: foo",
+            "    10- 10"
+        ]);
+}
+
+#[test]
+fn after_exhausted_line_range() {
+    // file reading is interrupted after the line range, but
+    // following lines are still read for the context
+    TestCase::new_for_stdout_tests()
+        .add_arg("-A2")
+        .add_file("a.py", lines!["foo", "2", "3", "foo"])
+        .add_named_rule(
+            "rule",
+            lines!["files a.py", "match /foo/ within first 1 line"],
+        )
+        .run()
+        .assert_stdout(lines![
+            "root",
+            "  a.py:1:1: rule",
+            "    1: foo",
+            "    2- 2",
+            "    3- 3"
+        ]);
+}
+
+#[test]
+fn deferred() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("-C1")
+        .add_file("a.py", lines!["foo", "2", "foo", "4"])
+        .add_named_rule(
+            "rule",
+            lines!["files a.py", "match /foo/ within last 2 lines"],
+        )
+        .run()
+        .assert_stdout(lines![
+            "root",
+            "  a.py:3:1: rule",
+            "    2- 2",
+            "    3: foo",
+            "    4- 4"
+        ]);
+}
+
+#[test]
+fn full_paths() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--format=full-paths")
+        .add_arg("-A1")
+        .add_file("a.py", lines!["foo", "bar"])
+        .add_named_rule("rule", lines!["files a.py", "match /foo/"])
+        .run()
+        .assert_stdout(paths!(lines![
+            "root/a.py:1:1: rule",
+            "  1: foo",
+            "  2- bar"
+        ]));
+}
+
+#[test]
+fn ignored_by_machine_readable_formats() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--format=jsonl")
+        .add_arg("-C1")
+        .add_file("a.py", lines!["foo", "bar"])
+        .add_named_rule("rule", lines!["files a.py", "match /foo/"])
+        .run()
+        .assert_stdout(lines![
+            r#"{"message":"rule","root":"root","file":"a.py","line":1,"column":1,"end_column":4,"text":"foo"}"#
+        ]);
+}
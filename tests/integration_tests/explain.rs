@@ -0,0 +1,102 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use testutils::{TestCase, lines};
+
+#[test]
+fn content_conditions() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--explain=rule")
+        .add_file("a.py", lines!["foo", "bar", "baz"])
+        .add_file("b.py", lines!["foo"])
+        .add_named_rule(
+            "rule",
+            lines!["files *.py", "lines > 1", "nomatch /baz/", "match /foo/"],
+        )
+        .run()
+        .assert_stdout(lines![
+            "root",
+            "  [-] rule: not matched",
+            "    [-] files *.py: matched 2 paths, first is a.py",
+            "      [-] a.py",
+            "        [+] lines > 1: file has at least 3 lines",
+            "        [-] nomatch /baz/: matched at line 3",
+            "        [+] match /foo/: matched at line 1",
+            "      [-] b.py",
+            "        [-] lines > 1: file has 1 lines",
+            "        [+] nomatch /baz/: not matched",
+            "        [+] match /foo/: matched at line 1"
+        ]);
+}
+
+#[test]
+fn matched() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--explain=rule")
+        .add_file("a.py", lines!["foo", "bar", "foo"])
+        .add_named_rule("rule", lines!["files *.py", "match /foo/"])
+        .run()
+        .assert_stdout(lines![
+            "root",
+            "  [+] rule: matched",
+            "    [+] files *.py: matched a.py",
+            "      [+] a.py",
+            "        [+] match /foo/: matched at lines 1, 3"
+        ]);
+}
+
+#[test]
+fn negative_condition() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--explain=rule")
+        .add_file("README", "")
+        .add_file("a.py", "")
+        .add_named_rule("rule", lines!["nofiles README", "files *.py"])
+        .run()
+        .assert_stdout(lines![
+            "root",
+            "  [-] rule: not matched",
+            "    [-] nofiles README: matched README",
+            "    [?] files *.py: no paths matched before evaluation stopped"
+        ]);
+}
+
+#[test]
+fn only_explained_rule_is_shown() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--explain=second")
+        .add_named_rule("first", "")
+        .add_named_rule("second", "")
+        .run()
+        .assert_stdout(lines![
+            "root",
+            "  [+] second: matched",
+            "    (rule has no conditions and always matches)"
+        ]);
+}
+
+#[test]
+fn unknown_rule() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--explain=unknown")
+        .add_named_rule("rule", "")
+        .silence_stderr()
+        .run()
+        .assert_failure()
+        .assert_stderr_contains("no rule with id or title");
+}
+
+#[test]
+fn rule_id_preferred_over_title() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--explain=dup")
+        .add_named_rule("dup", "")
+        .add_named_rule("dup", "")
+        .add_named_rule("third", lines!["id dup"])
+        .run()
+        .assert_stdout(lines![
+            "root",
+            "  [+] third: matched",
+            "    (rule has no conditions and always matches)"
+        ]);
+}
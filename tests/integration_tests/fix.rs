@@ -0,0 +1,205 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use testutils::{TestCase, lines};
+
+#[test]
+fn without_fix() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["foo(1)", "bar"])
+        .add_rule(lines![
+            "files *.py",
+            "match /foo\\((\\d+)\\)/",
+            "replace 'baz($1)'"
+        ])
+        .run()
+        .assert_matches(vec!["a.py:1"]);
+    assert_eq!(test_case.read_file("a.py"), lines!["foo(1)", "bar"]);
+}
+
+#[test]
+fn fix() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["foo(1)", "bar", "foo(2) foo(3)"])
+        .add_rule(lines![
+            "files *.py",
+            "match /foo\\((\\d+)\\)/",
+            "replace 'baz($1)'"
+        ])
+        .add_arg("--fix")
+        .silence_stderr()
+        .run()
+        .assert_stderr_contains("Applied 2 fix(es) for rule")
+        .assert_matches(vec![]);
+    assert_eq!(
+        test_case.read_file("a.py"),
+        lines!["baz(1)", "bar", "baz(2) baz(3)"]
+    );
+}
+
+#[test]
+fn braced_group_followed_by_text() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["foo(1)", "foo(2)"])
+        .add_rule(lines![
+            "files *.py",
+            "match /foo\\((\\d+)\\)/",
+            "replace 'bar(${1}0)'"
+        ])
+        .add_arg("--fix")
+        .silence_stderr()
+        .run()
+        .assert_matches(vec![]);
+    assert_eq!(test_case.read_file("a.py"), lines!["bar(10)", "bar(20)"]);
+}
+
+#[test]
+fn unbraced_group_followed_by_text() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["foo(1)"])
+        .add_rule(lines![
+            "files *.py",
+            "match /foo\\((\\d+)\\)/",
+            "replace 'bar($1x)'"
+        ])
+        .add_arg("--fix")
+        .silence_stderr()
+        .run()
+        .assert_matches(vec![]);
+    // `$1x` refers to group named `1x`, which does not exist
+    assert_eq!(test_case.read_file("a.py"), lines!["bar()"]);
+}
+
+#[test]
+fn line_endings_preserved() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", "foo\r\nfoo\nfoo")
+        .add_rule(lines!["files *.py", "match /foo/", "replace 'bar'"])
+        .add_arg("--fix")
+        .silence_stderr()
+        .run()
+        .assert_matches(vec![]);
+    assert_eq!(test_case.read_file("a.py"), "bar\r\nbar\nbar");
+}
+
+#[test]
+fn ignored_lines_not_fixed() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["foo", "foo  # omnilinter: ignore"])
+        .add_rule(lines!["files *.py", "match /foo/", "replace 'bar'"])
+        .add_arg("--fix")
+        .silence_stderr()
+        .run()
+        .assert_stderr_contains("Applied 1 fix(es) for rule")
+        .assert_matches(vec![]);
+    assert_eq!(
+        test_case.read_file("a.py"),
+        lines!["bar", "foo  # omnilinter: ignore"]
+    );
+}
+
+#[test]
+fn rules_without_replacement_reported() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["foo", "bar"])
+        .add_rule(lines!["files *.py", "match /foo/", "replace 'baz'"])
+        .add_rule(lines!["files *.py", "match /bar/"])
+        .add_arg("--fix")
+        .silence_stderr()
+        .run()
+        .assert_matches(vec!["a.py:2"]);
+    assert_eq!(test_case.read_file("a.py"), lines!["baz", "bar"]);
+}
+
+#[test]
+fn only_reported_condition_fixed() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["foo", "bar"])
+        .add_rule(lines![
+            "files *.py",
+            "match /foo/",
+            "replace 'baz'",
+            "match /bar/",
+            "replace 'qux'"
+        ])
+        .add_arg("--fix")
+        .silence_stderr()
+        .run()
+        .assert_matches(vec![]);
+    assert_eq!(test_case.read_file("a.py"), lines!["foo", "qux"]);
+}
+
+#[test]
+fn follow_up_patterns() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["try:", "    pass", "except:", "except:"])
+        .add_rule(lines![
+            "files *.py",
+            "match /^try:/ then /^except(:)/",
+            "replace 'except Exception$1'"
+        ])
+        .add_arg("--fix")
+        .silence_stderr()
+        .run()
+        .assert_stderr_contains("Applied 1 fix(es) for rule")
+        .assert_matches(vec![]);
+    assert_eq!(
+        test_case.read_file("a.py"),
+        lines!["try:", "    pass", "except Exception:", "except:"]
+    );
+}
+
+#[test]
+fn unapplied_fix_reported() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["foo"])
+        .add_rule(lines!["files *.py", "match /foo/", "replace '$0'"])
+        .add_arg("--fix")
+        .silence_stderr()
+        .run()
+        .assert_stderr_contains("a.py:1 for rule \"\", replacement does not change the line")
+        .assert_matches(vec!["a.py:1"]);
+    assert_eq!(test_case.read_file("a.py"), lines!["foo"]);
+}
+
+#[test]
+fn dry_run() {
+    let mut test_case = TestCase::new_for_stdout_tests();
+    test_case
+        .add_file("a.py", lines!["foo(1)", "bar"])
+        .add_rule(lines![
+            "files *.py",
+            "match /foo\\((\\d+)\\)/",
+            "replace 'baz($1)'"
+        ])
+        .add_arg("--fix")
+        .add_arg("--dry-run")
+        .silence_stderr()
+        .run()
+        .assert_stderr_contains("Would apply 1 fix(es) for rule")
+        .assert_stdout_contains(
+            "--- a/a.py\n+++ b/a.py\n@@ -1,2 +1,2 @@\n-foo(1)\n+baz(1)\n bar\n",
+        );
+    assert_eq!(test_case.read_file("a.py"), lines!["foo(1)", "bar"]);
+}
+
+#[test]
+fn dry_run_requires_fix() {
+    TestCase::new_for_stdout_tests()
+        .add_file("a.py", lines!["foo"])
+        .add_rule(lines!["files *.py", "match /foo/", "replace 'bar'"])
+        .add_arg("--dry-run")
+        .silence_stderr()
+        .run()
+        .assert_failure();
+}
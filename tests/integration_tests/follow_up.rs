@@ -0,0 +1,93 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use testutils::{TestCase, lines};
+
+#[test]
+fn reports_completing_line() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["#[test]", "fn foo() {}", "fn bar() {}"])
+        .add_rule(lines!["files a", "match /#\\[test\\]/ then /fn/"])
+        .run()
+        .assert_matches(vec!["a:2"]);
+}
+
+#[test]
+fn follow_up_before_start() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["fn foo() {}", "#[test]"])
+        .add_rule(lines!["files a", "match /#\\[test\\]/ then /fn/"])
+        .run()
+        .assert_matches(vec![]);
+}
+
+#[test]
+fn follow_up_on_same_line() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["#[test] fn foo() {}"])
+        .add_rule(lines!["files a", "match /#\\[test\\]/ then /fn/"])
+        .run()
+        .assert_matches(vec![]);
+}
+
+#[test]
+fn within_distance() {
+    TestCase::new_for_json_tests()
+        .add_file(
+            "a",
+            lines!["try:", "", "except:", "try:", "", "", "except:"],
+        )
+        .add_rule(lines![
+            "files a",
+            "match /try:/ then /except:/ within 2 lines"
+        ])
+        .run()
+        .assert_matches(vec!["a:3"]);
+}
+
+#[test]
+fn restarted_sequence() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a", "", "", "a", "b"])
+        .add_rule(lines!["files a", "match /a/ then /b/ within 1 line"])
+        .run()
+        .assert_matches(vec!["a:5"]);
+}
+
+#[test]
+fn multiple_sequences() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a", "b", "b", "a", "b"])
+        .add_rule(lines!["files a", "match /a/ then /b/"])
+        .run()
+        .assert_matches(vec!["a:2", "a:5"]);
+}
+
+#[test]
+fn follow_up_exclusions() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a", "bc", "b"])
+        .add_rule(lines!["files a", "match /a/ then /b/ !/c/"])
+        .run()
+        .assert_matches(vec!["a:3"]);
+}
+
+#[test]
+fn with_count() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a", "b", "a", "b"])
+        .add_file("b", lines!["a", "b", "b"])
+        .add_rule(lines!["files *", "match /a/ then /b/", "matches >= 2"])
+        .run()
+        .assert_matches(vec!["a:2", "a:4"]);
+}
+
+#[test]
+fn not_reporting_target() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a", "b", "c"])
+        .add_file("b", lines!["b", "a", "c"])
+        .add_rule(lines!["files *", "match /a/ then /b/", "match /c/"])
+        .run()
+        .assert_matches(vec!["a:3"]);
+}
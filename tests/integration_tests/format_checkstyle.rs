@@ -0,0 +1,47 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use testutils::{TestCase, lines, paths};
+
+#[test]
+fn basic() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--format=checkstyle")
+        .add_file("a.py", lines!["foo", "foo"])
+        .add_file("b.py", lines![""])
+        .add_named_rule("root <rule>", lines!["id root", "tags minor"])
+        .add_named_rule("file", lines!["files b.py"])
+        .add_named_rule("line \"rule\"", lines!["tags error", "files a.py", "match /foo/"])
+        .run()
+        .assert_stdout(
+            &lines![
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+                "<checkstyle version=\"4.3\">",
+                "  <file name=\"root\">",
+                "    <error severity=\"info\" message=\"root &lt;rule&gt;\" source=\"omnilinter.root\"/>",
+                "  </file>",
+                "  <file name=\"root/a.py\">",
+                "    <error line=\"1\" severity=\"error\" message=\"line &quot;rule&quot;\" source=\"omnilinter.line &quot;rule&quot;\"/>",
+                "    <error line=\"2\" severity=\"error\" message=\"line &quot;rule&quot;\" source=\"omnilinter.line &quot;rule&quot;\"/>",
+                "  </file>",
+                "  <file name=\"root/b.py\">",
+                "    <error severity=\"warning\" message=\"file\" source=\"omnilinter.file\"/>",
+                "  </file>",
+                "</checkstyle>"
+            ]
+            .replace("root/", paths!("root/")),
+        );
+}
+
+#[test]
+fn empty() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--format=checkstyle")
+        .add_rule(lines!["files *.py"])
+        .run()
+        .assert_stdout(lines![
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+            "<checkstyle version=\"4.3\">",
+            "</checkstyle>"
+        ]);
+}
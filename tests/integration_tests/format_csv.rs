@@ -0,0 +1,36 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use testutils::{TestCase, lines};
+
+#[test]
+fn csv() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--format=csv")
+        .add_file("a.py", lines!["foo"])
+        .add_named_rule("root, \"quoted\"", lines!["tags b,a"])
+        .add_named_rule("file", lines!["files a.py"])
+        .add_named_rule("line", lines!["files a.py", "match /foo/"])
+        .run()
+        .assert_stdout(lines![
+            "root,path,line,rule,tags",
+            "root,,,\"root, \"\"quoted\"\"\",\"a,b\"",
+            "root,a.py,,file,",
+            "root,a.py,1,line,"
+        ]);
+}
+
+#[test]
+fn tsv() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--format=tsv")
+        .add_file("a.py", lines!["foo"])
+        .add_named_rule("root, \"quoted\"", lines!["tags b,a"])
+        .add_named_rule("line", lines!["files a.py", "match /foo/"])
+        .run()
+        .assert_stdout(lines![
+            "root\tpath\tline\trule\ttags",
+            "root\t\t\t\"root, \"\"quoted\"\"\"\ta,b",
+            "root\ta.py\t1\tline\t"
+        ]);
+}
@@ -0,0 +1,34 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use testutils::{TestCase, lines};
+
+#[test]
+fn basic() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--format=github")
+        .set_env("GITHUB_WORKSPACE", ".")
+        .add_file("a.py", lines!["foo"])
+        .add_named_rule("root", lines!["tags minor"])
+        .add_named_rule("file", lines!["id file-rule", "files a.py"])
+        .add_named_rule("line", lines!["tags error", "files a.py", "match /foo/"])
+        .run()
+        .assert_stdout(lines![
+            "::notice title=omnilinter::root (in root)",
+            "::warning file=root/a.py,title=omnilinter [file-rule]::file",
+            "::error file=root/a.py,line=1,title=omnilinter::line"
+        ]);
+}
+
+#[test]
+fn escaping() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--format=github")
+        .set_env("GITHUB_WORKSPACE", ".")
+        .add_file("a,b.py", lines![""])
+        .add_named_rule("100% wrong: files", lines!["files *.py"])
+        .run()
+        .assert_stdout(lines![
+            "::warning file=root/a%2Cb.py,title=omnilinter::100%25 wrong: files"
+        ]);
+}
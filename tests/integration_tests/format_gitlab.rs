@@ -0,0 +1,73 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use testutils::{TestCase, lines};
+
+fn get_fingerprints(report: &serde_json::Value) -> Vec<&str> {
+    report
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|issue| issue["fingerprint"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn basic() {
+    let report = TestCase::new_for_stdout_tests()
+        .add_arg("--format=gitlab")
+        .add_file("a.py", lines!["foo"])
+        .add_named_rule("root", lines!["tags minor"])
+        .add_named_rule(
+            "line",
+            lines!["id line-rule", "tags error", "files a.py", "match /foo/"],
+        )
+        .run()
+        .stdout_json();
+
+    assert_eq!(report[0]["description"], "root");
+    assert_eq!(report[0]["check_name"], "root");
+    assert_eq!(report[0]["severity"], "minor");
+    assert_eq!(report[0]["location"]["path"], ".");
+    assert_eq!(report[0]["location"]["lines"]["begin"], 1);
+
+    assert_eq!(report[1]["description"], "line");
+    assert_eq!(report[1]["check_name"], "line-rule");
+    assert_eq!(report[1]["severity"], "critical");
+    assert_eq!(report[1]["location"]["path"], "a.py");
+    assert_eq!(report[1]["location"]["lines"]["begin"], 1);
+}
+
+#[test]
+fn fingerprints_stable() {
+    let mut test_case = TestCase::new_for_stdout_tests();
+    test_case
+        .add_arg("--format=gitlab")
+        .add_file("a.py", lines!["foo", "foo"])
+        .add_rule(lines!["files a.py", "match /foo/"]);
+
+    let report = test_case.run().stdout_json();
+    let fingerprints = get_fingerprints(&report);
+    assert_ne!(fingerprints[0], fingerprints[1]);
+
+    let shifted_report = test_case
+        .add_file("a.py", lines!["bar", "  foo", "bar", "foo"])
+        .run()
+        .stdout_json();
+    assert_eq!(get_fingerprints(&shifted_report), fingerprints);
+}
+
+#[test]
+fn fingerprints_use_whole_line() {
+    let long_prefix = "x".repeat(1024);
+    let report = TestCase::new_for_stdout_tests()
+        .add_arg("--format=gitlab")
+        .add_file("a.py", &format!("{long_prefix}foo\n{long_prefix}bar\n"))
+        .add_rule(lines!["files a.py", "match /x/"])
+        .run()
+        .stdout_json();
+
+    let fingerprints = get_fingerprints(&report);
+    assert_eq!(fingerprints.len(), 2);
+    assert_ne!(fingerprints[0], fingerprints[1]);
+}
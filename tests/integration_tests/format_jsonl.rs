@@ -0,0 +1,44 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use testutils::{TestCase, lines};
+
+#[test]
+fn basic() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--format=jsonl")
+        .add_file("a.py", lines!["foo", "foo"])
+        .add_named_rule("root", "")
+        .add_named_rule("line", lines!["files a.py", "match /foo/"])
+        .run()
+        .assert_stdout(lines![
+            r#"{"message":"root","root":"root"}"#,
+            r#"{"message":"line","root":"root","file":"a.py","line":1,"column":1,"end_column":4,"text":"foo"}"#,
+            r#"{"message":"line","root":"root","file":"a.py","line":2,"column":1,"end_column":4,"text":"foo"}"#
+        ]);
+}
+
+#[test]
+fn no_matches() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--format=jsonl")
+        .add_rule(lines!["files *.py"])
+        .run()
+        .assert_stdout("");
+}
+
+#[test]
+fn with_fix() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--format=jsonl")
+        .add_arg("--fix")
+        .add_file("a.py", lines!["foo", "bar"])
+        .add_rule(lines!["files a.py", "match /foo|bar/", "replace 'baz'"])
+        .add_rule(lines!["files a.py", "match /foo|bar/"])
+        .silence_stderr()
+        .run()
+        .assert_stdout(lines![
+            r#"{"message":"","root":"root","file":"a.py","line":1,"column":1,"end_column":4,"text":"foo"}"#,
+            r#"{"message":"","root":"root","file":"a.py","line":2,"column":1,"end_column":4,"text":"bar"}"#
+        ]);
+}
@@ -0,0 +1,49 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use testutils::{TestCase, lines, paths};
+
+#[test]
+fn basic() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--format=junit")
+        .add_file("a.py", lines!["foo", "foo"])
+        .add_named_rule("root <rule>", "")
+        .add_named_rule("no match", lines!["files *.c"])
+        .add_named_rule("line", lines!["id line-rule", "files a.py", "match /foo/"])
+        .run()
+        .assert_stdout(
+            &lines![
+                "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+                "<testsuites name=\"omnilinter\" tests=\"3\" failures=\"2\">",
+                "  <testsuite name=\"root\" tests=\"3\" failures=\"2\">",
+                "    <testcase name=\"root &lt;rule&gt;\" classname=\"root\">",
+                "      <failure message=\"1 match(es)\" type=\"root &lt;rule&gt;\">root</failure>",
+                "    </testcase>",
+                "    <testcase name=\"no match\" classname=\"root\"/>",
+                "    <testcase name=\"line\" classname=\"root\">",
+                "      <failure message=\"2 match(es)\" type=\"line-rule\">root/a.py:1",
+                "root/a.py:2</failure>",
+                "    </testcase>",
+                "  </testsuite>",
+                "</testsuites>"
+            ]
+            .replace("root/", paths!("root/")),
+        );
+}
+
+#[test]
+fn no_matches() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--format=junit")
+        .add_named_rule("rule", lines!["files *.py"])
+        .run()
+        .assert_stdout(lines![
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>",
+            "<testsuites name=\"omnilinter\" tests=\"1\" failures=\"0\">",
+            "  <testsuite name=\"root\" tests=\"1\" failures=\"0\">",
+            "    <testcase name=\"rule\" classname=\"root\"/>",
+            "  </testsuite>",
+            "</testsuites>"
+        ]);
+}
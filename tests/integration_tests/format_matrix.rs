@@ -0,0 +1,29 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use testutils::{TestCase, lines};
+
+#[test]
+fn csv_when_not_tty() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--format=matrix")
+        .add_file("a.py", lines!["foo", "foo"])
+        .add_named_rule("root, rule", "")
+        .add_named_rule("unmatched", lines!["files b.py"])
+        .add_named_rule("line", lines!["id line-rule", "files a.py", "match /foo/"])
+        .run()
+        .assert_stdout(lines![
+            "root,\"root, rule\",line-rule,total",
+            "root,1,2,3",
+            "total,1,2,3"
+        ]);
+}
+
+#[test]
+fn roots_without_matches() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--format=matrix")
+        .add_rule(lines!["files *.py"])
+        .run()
+        .assert_stdout(lines!["root,total", "root,0", "total,0"]);
+}
@@ -0,0 +1,84 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use testutils::{TestCase, lines};
+
+#[test]
+fn markdown() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--format=markdown")
+        .add_file("a.py", lines!["foo", "foo"])
+        .add_named_rule("root | <rule>", "")
+        .add_named_rule("line", lines!["files a.py", "match /foo/"])
+        .run()
+        .assert_stdout(lines![
+            "# omnilinter report",
+            "",
+            "3 match(es) in 1 root(s).",
+            "",
+            "## Matches per rule",
+            "",
+            "| Rule | Matches |",
+            "| --- | ---: |",
+            "| root \\| \\<rule\\> | 1 |",
+            "| line | 2 |",
+            "| **Total** | **3** |",
+            "",
+            "## Matches per root",
+            "",
+            "| Root | Matches |",
+            "| --- | ---: |",
+            "| root | 3 |",
+            "| **Total** | **3** |",
+            "",
+            "## Matches",
+            "",
+            "<details>",
+            "<summary>root (3)</summary>",
+            "",
+            "- root \\| \\<rule\\>",
+            "- `a.py:1`: line",
+            "- `a.py:2`: line",
+            "",
+            "</details>",
+            ""
+        ]);
+}
+
+#[test]
+fn markdown_empty() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--format=markdown")
+        .add_rule(lines!["files *.py"])
+        .run()
+        .assert_stdout(lines![
+            "# omnilinter report",
+            "",
+            "0 match(es) in 0 root(s).",
+            ""
+        ]);
+}
+
+#[test]
+fn html() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--format=html")
+        .add_file("a.py", lines!["foo"])
+        .add_named_rule("root <rule>", "")
+        .add_named_rule("line", lines!["files a.py", "match /foo/"])
+        .run()
+        .assert_stdout_contains("<!DOCTYPE html>")
+        .assert_stdout_contains("<p>2 match(es) in 1 root(s).</p>")
+        .assert_stdout_contains("<tr><td>root &lt;rule&gt;</td><td class=\"count\">1</td></tr>")
+        .assert_stdout_contains("<tr><td>root</td><td class=\"count\">2</td></tr>")
+        .assert_stdout_contains(lines![
+            "<details>",
+            "<summary>root (2)</summary>",
+            "<ul>",
+            "<li>root &lt;rule&gt;</li>",
+            "<li><code>a.py:1</code>: line</li>",
+            "</ul>",
+            "</details>"
+        ])
+        .assert_stdout_contains("</html>");
+}
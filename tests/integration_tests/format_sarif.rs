@@ -0,0 +1,142 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use serde_json::json;
+use testutils::{TestCase, lines};
+
+#[test]
+fn rules() {
+    let sarif = TestCase::new_for_stdout_tests()
+        .add_arg("--format=sarif")
+        .add_named_rule("first", lines!["id rule-1", "tags error,style"])
+        .add_named_rule("second", lines!["files *.py"])
+        .run()
+        .stdout_json();
+
+    assert_eq!(sarif["version"], "2.1.0");
+    assert_eq!(
+        sarif["runs"][0]["tool"]["driver"]["rules"],
+        json!([
+            {
+                "id": "rule-1",
+                "shortDescription": {"text": "first"},
+                "defaultConfiguration": {"level": "error"},
+                "properties": {"tags": ["error", "style"]},
+            },
+            {
+                "id": "second",
+                "shortDescription": {"text": "second"},
+            },
+        ])
+    );
+}
+
+#[test]
+fn results() {
+    let sarif = TestCase::new_for_stdout_tests()
+        .add_arg("--format=sarif")
+        .add_file("a.py", lines!["foo"])
+        .add_named_rule("root", "")
+        .add_named_rule("file", lines!["files *.py"])
+        .add_named_rule("line", lines!["files *.py", "match /foo/"])
+        .run()
+        .stdout_json();
+
+    let run = &sarif["runs"][0];
+    assert!(
+        run["originalUriBaseIds"]["ROOT0"]["uri"]
+            .as_str()
+            .unwrap()
+            .ends_with("/root/")
+    );
+    assert_eq!(
+        run["results"],
+        json!([
+            {
+                "ruleId": "root",
+                "ruleIndex": 0,
+                "message": {"text": "root"},
+                "locations": [{"physicalLocation": {
+                    "artifactLocation": {"uri": "./", "uriBaseId": "ROOT0"},
+                }}],
+            },
+            {
+                "ruleId": "file",
+                "ruleIndex": 1,
+                "message": {"text": "file"},
+                "locations": [{"physicalLocation": {
+                    "artifactLocation": {"uri": "a.py", "uriBaseId": "ROOT0"},
+                }}],
+            },
+            {
+                "ruleId": "line",
+                "ruleIndex": 2,
+                "message": {"text": "line"},
+                "locations": [{"physicalLocation": {
+                    "artifactLocation": {"uri": "a.py", "uriBaseId": "ROOT0"},
+                    "region": {"startLine": 1, "startColumn": 1, "endColumn": 4},
+                }}],
+            },
+        ])
+    );
+}
+
+#[test]
+fn columns_in_code_points() {
+    let sarif = TestCase::new_for_stdout_tests()
+        .add_arg("--format=sarif")
+        .add_file("a.py", lines!["äö foo"])
+        .add_rule(lines!["files *.py", "match /foo/"])
+        .run()
+        .stdout_json();
+
+    let run = &sarif["runs"][0];
+    assert_eq!(run["columnKind"], "unicodeCodePoints");
+    assert_eq!(
+        run["results"][0]["locations"][0]["physicalLocation"]["region"],
+        json!({"startLine": 1, "startColumn": 4, "endColumn": 7})
+    );
+}
+
+#[test]
+fn uri_escaping() {
+    let sarif = TestCase::new_for_stdout_tests()
+        .add_arg("--format=sarif")
+        .add_file("a b%.py", lines!["foo"])
+        .add_rule(lines!["files *.py"])
+        .run()
+        .stdout_json();
+
+    assert_eq!(
+        sarif["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+        "a%20b%25.py"
+    );
+}
+
+#[test]
+fn duplicate_rule_ids() {
+    let sarif = TestCase::new_for_stdout_tests()
+        .add_arg("--format=sarif")
+        .add_named_rule("unique", "")
+        .add_named_rule("dup", "")
+        .add_named_rule("dup", "")
+        .run()
+        .stdout_json();
+
+    let run = &sarif["runs"][0];
+    let rule_ids: Vec<_> = run["tool"]["driver"]["rules"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|rule| rule["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(rule_ids, vec!["unique", "dup#1", "dup#2"]);
+
+    let result_ids: Vec<_> = run["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|result| result["ruleId"].as_str().unwrap())
+        .collect();
+    assert_eq!(result_ids, vec!["unique", "dup#1", "dup#2"]);
+}
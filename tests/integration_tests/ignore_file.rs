@@ -0,0 +1,145 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use testutils::{TestCase, lines, paths};
+
+#[test]
+fn ignore_whole_file() {
+    TestCase::new_for_json_tests()
+        .add_file(".omnilinterignore", lines!["first a.py"])
+        .add_file("a.py", lines!["foo", "foo"])
+        .add_file("b.py", lines!["foo"])
+        .add_named_rule("first", lines!["id first", "files *.py", "match /foo/"])
+        .run()
+        .assert_matches(vec!["b.py:1"]);
+}
+
+#[test]
+fn ignore_line() {
+    TestCase::new_for_json_tests()
+        .add_file(".omnilinterignore", lines!["first a.py:2"])
+        .add_file("a.py", lines!["foo", "foo"])
+        .add_named_rule("first", lines!["id first", "files *.py", "match /foo/"])
+        .run()
+        .assert_matches(vec!["a.py:1"]);
+}
+
+#[test]
+fn ignore_file_match() {
+    TestCase::new_for_json_tests()
+        .add_file(".omnilinterignore", lines!["first a.py"])
+        .add_file("a.py", lines!["foo"])
+        .add_file("b.py", lines!["foo"])
+        .add_named_rule("first", lines!["id first", "files *.py", "nomatch /bar/"])
+        .run()
+        .assert_matches(vec!["b.py"]);
+}
+
+#[test]
+fn glob() {
+    TestCase::new_for_json_tests()
+        .add_file(".omnilinterignore", lines!["first vendor/**/*.py"])
+        .add_file("vendor/a.py", lines!["foo"])
+        .add_file("vendor/lib/b.py", lines!["foo"])
+        .add_file("src/c.py", lines!["foo"])
+        .add_named_rule("first", lines!["id first", "files *.py", "match /foo/"])
+        .run()
+        .assert_matches(vec!["src/c.py:1"]);
+}
+
+#[test]
+fn other_rules_not_suppressed() {
+    TestCase::new_for_json_tests()
+        .add_file(".omnilinterignore", lines!["first a.py"])
+        .add_file("a.py", lines!["foo"])
+        .add_named_rule("first", lines!["id first", "files *.py", "match /foo/"])
+        .add_named_rule("second", lines!["id second", "files *.py", "match /foo/"])
+        .run()
+        .assert_stdout_contains("second")
+        .assert_matches(vec!["a.py:1"]);
+}
+
+#[test]
+fn tags_and_wildcard() {
+    TestCase::new_for_json_tests()
+        .add_file(".omnilinterignore", lines!["style a.py", "* b.py"])
+        .add_file("a.py", lines!["foo"])
+        .add_file("b.py", lines!["foo"])
+        .add_named_rule("first", lines!["tags style", "files *.py", "match /foo/"])
+        .add_named_rule("second", lines!["tags bugs", "files *.py", "match /foo/"])
+        .run()
+        .assert_stdout_contains("second")
+        .assert_matches(vec!["a.py:1"]);
+}
+
+#[test]
+fn comments() {
+    TestCase::new_for_json_tests()
+        .add_file(
+            ".omnilinterignore",
+            lines!["# third-party code", "", "first a.py  # comment"],
+        )
+        .add_file("a.py", lines!["foo"])
+        .add_named_rule("first", lines!["id first", "files *.py", "match /foo/"])
+        .run()
+        .assert_success()
+        .assert_matches(vec![]);
+}
+
+#[test]
+fn stale_entry() {
+    TestCase::new_for_json_tests()
+        .add_file(".omnilinterignore", lines!["first a.py", "first b.py:1"])
+        .add_file("a.py", lines!["foo"])
+        .add_file("b.py", lines!["bar"])
+        .add_named_rule("first", lines!["id first", "files *.py", "match /foo/"])
+        .silence_stderr()
+        .run()
+        .assert_stderr_contains(".omnilinterignore:2: entry does not match anything")
+        .assert_matches(vec![]);
+}
+
+#[test]
+fn entry_for_skipped_rule_not_stale() {
+    TestCase::new_for_json_tests()
+        .add_file(".omnilinterignore", lines!["second a.py", "* b.py"])
+        .add_file("a.py", lines!["foo"])
+        .add_named_rule("first", lines!["tags t1", "files *.py", "match /foo/"])
+        .add_named_rule(
+            "second",
+            lines!["id second", "tags t2", "files *.py", "match /foo/"],
+        )
+        .add_arg("--tags=t1")
+        .silence_stderr()
+        .run()
+        .assert_stderr_not_contains(".omnilinterignore:1:")
+        .assert_stderr_contains(".omnilinterignore:2: entry does not match anything")
+        .assert_matches(vec!["a.py:1"]);
+}
+
+#[test]
+fn stale_entries_reported_once_in_order() {
+    TestCase::new_for_json_tests()
+        .add_file(".omnilinterignore", lines!["* b.py"])
+        .add_raw_file("root2/.omnilinterignore", lines!["* c.py"])
+        .add_arg("root2")
+        .add_rule(lines!["files *.py"])
+        .silence_stderr()
+        .run()
+        .assert_stderr_contains(paths!(lines![
+            "Warning: root/.omnilinterignore:1: entry does not match anything and may be removed",
+            "Warning: root2/.omnilinterignore:1: entry does not match anything and may be removed"
+        ]));
+}
+
+#[test]
+fn invalid_entry() {
+    TestCase::new_for_json_tests()
+        .add_file(".omnilinterignore", lines!["first", "first a.py"])
+        .add_file("a.py", lines!["foo"])
+        .add_named_rule("first", lines!["id first", "files *.py", "match /foo/"])
+        .silence_stderr()
+        .run()
+        .assert_stderr_contains(".omnilinterignore:1: skipping invalid entry")
+        .assert_matches(vec![]);
+}
@@ -21,6 +21,21 @@ fn match_with_marker() {
         .assert_matches(vec!["a.py:1"]);
 }
 
+#[test]
+fn match_with_unknown_ignore_marker() {
+    TestCase::new_for_json_tests()
+        .add_file(
+            "a.py",
+            lines![
+                "foo  # omnilinter: ignored",
+                "bar  # omnilinter: ignore-this"
+            ],
+        )
+        .add_rule(lines!["files *.py", "match /foo|bar/"])
+        .run()
+        .assert_matches(vec![]);
+}
+
 #[test]
 fn nomatch_without_marker() {
     TestCase::new_for_json_tests()
@@ -38,3 +53,156 @@ fn nomatch_with_marker() {
         .run()
         .assert_matches(vec!["a.py"]);
 }
+
+#[test]
+fn marker_for_rule_id() {
+    TestCase::new_for_json_tests()
+        .add_file("a.py", lines!["foo  # omnilinter: ignore[first]"])
+        .add_named_rule("first", lines!["id first", "files *.py", "match /foo/"])
+        .add_named_rule("second", lines!["id second", "files *.py", "match /foo/"])
+        .run()
+        .assert_matches(vec!["a.py:1"]);
+}
+
+#[test]
+fn marker_for_rule_tag() {
+    TestCase::new_for_json_tests()
+        .add_file("a.py", lines!["foo  # omnilinter: ignore[style, other]"])
+        .add_named_rule("first", lines!["tags style", "files *.py", "match /foo/"])
+        .add_named_rule("second", lines!["tags bugs", "files *.py", "match /foo/"])
+        .run()
+        .assert_stdout_contains("second")
+        .assert_matches(vec!["a.py:1"]);
+}
+
+#[test]
+fn marker_for_other_rule() {
+    TestCase::new_for_json_tests()
+        .add_file("a.py", lines!["foo  # omnilinter: ignore[other]"])
+        .add_named_rule("first", lines!["id first", "files *.py", "match /foo/"])
+        .run()
+        .assert_matches(vec!["a.py:1"]);
+}
+
+#[test]
+fn next_line_marker() {
+    TestCase::new_for_json_tests()
+        .add_file(
+            "a.py",
+            lines!["# omnilinter: ignore-next-line", "foo", "foo"],
+        )
+        .add_rule(lines!["files *.py", "match /foo/"])
+        .run()
+        .assert_matches(vec!["a.py:3"]);
+}
+
+#[test]
+fn next_line_marker_for_rule_id() {
+    TestCase::new_for_json_tests()
+        .add_file(
+            "a.py",
+            lines!["# omnilinter: ignore-next-line[first]", "foo"],
+        )
+        .add_named_rule("first", lines!["id first", "files *.py", "match /foo/"])
+        .add_named_rule("second", lines!["id second", "files *.py", "match /foo/"])
+        .run()
+        .assert_stdout_contains("second")
+        .assert_matches(vec!["a.py:2"]);
+}
+
+#[test]
+fn disabled_block() {
+    TestCase::new_for_json_tests()
+        .add_file(
+            "a.py",
+            lines![
+                "foo",
+                "# omnilinter: disable",
+                "foo",
+                "# omnilinter: enable",
+                "foo"
+            ],
+        )
+        .add_rule(lines!["files *.py", "match /foo/"])
+        .run()
+        .assert_matches(vec!["a.py:1", "a.py:5"]);
+}
+
+#[test]
+fn disabled_block_until_end_of_file() {
+    TestCase::new_for_json_tests()
+        .add_file("a.py", lines!["foo", "# omnilinter: disable", "foo", "foo"])
+        .add_rule(lines!["files *.py", "match /foo/"])
+        .run()
+        .assert_matches(vec!["a.py:1"]);
+}
+
+#[test]
+fn disabled_block_for_rule_id() {
+    TestCase::new_for_json_tests()
+        .add_file(
+            "a.py",
+            lines![
+                "# omnilinter: disable[first]",
+                "foo",
+                "# omnilinter: enable[first]",
+                "foo"
+            ],
+        )
+        .add_named_rule("first", lines!["id first", "files *.py", "match /foo/"])
+        .add_named_rule("second", lines!["id second", "files *.py", "match /foo/"])
+        .run()
+        .assert_matches(vec!["a.py:2", "a.py:4", "a.py:4"]);
+}
+
+#[test]
+fn ignore_file_line_matches() {
+    TestCase::new_for_json_tests()
+        .add_file("a.py", lines!["# omnilinter: ignore-file", "foo"])
+        .add_file("b.py", lines!["foo"])
+        .add_rule(lines!["files *.py", "match /foo/"])
+        .run()
+        .assert_matches(vec!["b.py:1"]);
+}
+
+#[test]
+fn ignore_file_file_matches() {
+    TestCase::new_for_json_tests()
+        .add_file("a.py", lines!["# omnilinter: ignore-file", "foo"])
+        .add_file("b.py", lines!["foo"])
+        .add_rule(lines!["files *.py"])
+        .run()
+        .assert_matches(vec!["b.py"]);
+}
+
+#[test]
+fn ignore_file_nomatch() {
+    TestCase::new_for_json_tests()
+        .add_file("a.py", lines!["# omnilinter: ignore-file[first]", "foo"])
+        .add_named_rule("first", lines!["id first", "files *.py", "nomatch /bar/"])
+        .add_named_rule("second", lines!["id second", "files *.py", "nomatch /bar/"])
+        .run()
+        .assert_stdout_contains("second")
+        .assert_matches(vec!["a.py"]);
+}
+
+#[test]
+fn ignore_file_after_conditions_settled() {
+    TestCase::new_for_json_tests()
+        .add_file("a.py", lines!["foo", "bar", "# omnilinter: ignore-file"])
+        .add_rule(lines!["files *.py", "lines > 0"])
+        .run()
+        .assert_matches(vec![]);
+}
+
+#[test]
+fn ignore_file_too_far() {
+    TestCase::new_for_json_tests()
+        .add_file(
+            "a.py",
+            &(lines!["foo"].repeat(10) + lines!["# omnilinter: ignore-file"]),
+        )
+        .add_rule(lines!["files *.py"])
+        .run()
+        .assert_matches(vec!["a.py"]);
+}
@@ -0,0 +1,133 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use testutils::{TestCase, lines};
+
+#[test]
+fn match_within_first_lines() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a", "a", "a", "a"])
+        .add_rule(lines!["files a", "match /a/ within first 2 lines"])
+        .run()
+        .assert_matches(vec!["a:1", "a:2"]);
+}
+
+#[test]
+fn match_within_first_lines_not_satisfied() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["b", "b", "a"])
+        .add_rule(lines!["files a", "match /a/ within first 2 lines"])
+        .run()
+        .assert_matches(vec![]);
+}
+
+#[test]
+fn match_within_last_lines() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a", "a", "a", "a"])
+        .add_rule(lines!["files a", "match /a/ within last 2 lines"])
+        .run()
+        .assert_matches(vec!["a:3", "a:4"]);
+}
+
+#[test]
+fn match_within_last_lines_not_satisfied() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a", "b", "b"])
+        .add_rule(lines!["files a", "match /a/ within last 2 lines"])
+        .run()
+        .assert_matches(vec![]);
+}
+
+#[test]
+fn match_within_span() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a", "a", "a", "a"])
+        .add_rule(lines!["files a", "match /a/ within lines 2..3"])
+        .run()
+        .assert_matches(vec!["a:2", "a:3"]);
+}
+
+#[test]
+fn match_within_single_line() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a", "a"])
+        .add_rule(lines!["files a", "match /a/ within first 1 line"])
+        .run()
+        .assert_matches(vec!["a:1"]);
+}
+
+#[test]
+fn nomatch_within_first_lines() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["b", "b", "a"])
+        .add_file("b", lines!["a", "b", "b"])
+        .add_rule(lines!["files *", "nomatch /a/ within first 2 lines"])
+        .run()
+        .assert_matches(vec!["a"]);
+}
+
+#[test]
+fn nomatch_within_last_lines() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["b", "b", "a"])
+        .add_file("b", lines!["a", "b", "b"])
+        .add_rule(lines!["files *", "nomatch /a/ within last 2 lines"])
+        .run()
+        .assert_matches(vec!["b"]);
+}
+
+#[test]
+fn precondition_within_first_lines() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["#!/bin/sh", "foo"])
+        .add_file("b", lines!["foo", "#!/bin/sh", "foo"])
+        .add_rule(lines![
+            "files *",
+            "match /^#!/ within first 1 line",
+            "match /foo/"
+        ])
+        .run()
+        .assert_matches(vec!["a:2"]);
+}
+
+#[test]
+fn precondition_within_last_lines() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["foo", "end"])
+        .add_file("b", lines!["foo", "end", "more"])
+        .add_rule(lines![
+            "files *",
+            "match /end/ within last 1 line",
+            "match /foo/"
+        ])
+        .run()
+        .assert_matches(vec!["a:1"]);
+}
+
+#[test]
+fn range_longer_than_file() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a"])
+        .add_rule(lines!["files a", "match /a/ within last 10 lines"])
+        .run()
+        .assert_matches(vec!["a:1"]);
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a"])
+        .add_rule(lines!["files a", "match /a/ within lines 1..10"])
+        .run()
+        .assert_matches(vec!["a:1"]);
+}
+
+#[test]
+fn with_lines_condition() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a", "b", "c", "d"])
+        .add_rule(lines![
+            "files a",
+            "nomatch /c/ within first 2 lines",
+            "lines >= 4"
+        ])
+        .run()
+        .assert_matches(vec!["a"]);
+}
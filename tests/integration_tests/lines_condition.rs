@@ -125,3 +125,36 @@ fn not_equal_not_matching() {
         .run()
         .assert_matches(vec![]);
 }
+
+#[test]
+fn range_matching() {
+    TestCase::new_for_json_tests()
+        .add_file("a", "a\nb\nc\n")
+        .add_rule(lines!["files a", "lines 2..3"])
+        .run()
+        .assert_matches(vec!["a"]);
+}
+
+#[test]
+fn range_not_matching() {
+    // must not be satisfied early when lower bound is reached
+    TestCase::new_for_json_tests()
+        .add_file("a", "a\nb\nc\nd\n")
+        .add_rule(lines!["files a", "lines 2..3"])
+        .run()
+        .assert_matches(vec![]);
+}
+
+#[test]
+fn range_with_match() {
+    TestCase::new_for_json_tests()
+        .add_file("a", "a\nb\nc\nd\n")
+        .add_rule(lines!["files a", "lines 1..3", "match /a/"])
+        .run()
+        .assert_matches(vec![]);
+    TestCase::new_for_json_tests()
+        .add_file("a", "a\nb\nc\n")
+        .add_rule(lines!["files a", "lines 1..3", "match /a/"])
+        .run()
+        .assert_matches(vec!["a:1"]);
+}
@@ -0,0 +1,94 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use testutils::{TestCase, lines};
+
+#[test]
+fn reports_all_lines() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a", "b", "a", "a"])
+        .add_rule(lines!["files a", "match /a/", "matches > 2"])
+        .run()
+        .assert_matches(vec!["a:1", "a:3", "a:4"]);
+}
+
+#[test]
+fn not_enough_matches() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a", "b", "a"])
+        .add_rule(lines!["files a", "match /a/", "matches > 2"])
+        .run()
+        .assert_matches(vec![]);
+}
+
+#[test]
+fn too_many_matches() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a", "b", "a"])
+        .add_file("b", lines!["a", "b", "b"])
+        .add_rule(lines!["files *", "match /a/", "matches == 1"])
+        .run()
+        .assert_matches(vec!["b:1"]);
+}
+
+#[test]
+fn zero_matches() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a"])
+        .add_file("b", lines!["b"])
+        .add_rule(lines!["files *", "match /a/", "matches == 0", "lines >= 0"])
+        .run()
+        .assert_matches(vec!["b"]);
+}
+
+#[test]
+fn range() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a"])
+        .add_file("b", lines!["a", "a"])
+        .add_file("c", lines!["a", "a", "a"])
+        .add_rule(lines!["files *", "match /a/", "matches 2..3", "lines >= 0"])
+        .run()
+        .assert_matches(vec!["b", "c"]);
+}
+
+#[test]
+fn not_reporting_target() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a", "b", "a", "a"])
+        .add_file("b", lines!["a", "b"])
+        .add_rule(lines!["files *", "match /a/", "matches >= 2", "match /b/"])
+        .run()
+        .assert_matches(vec!["a:2"]);
+}
+
+#[test]
+fn with_line_range() {
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a", "a", "a", "a"])
+        .add_rule(lines![
+            "files a",
+            "match /a/ within first 3 lines",
+            "matches == 3"
+        ])
+        .run()
+        .assert_matches(vec!["a:1", "a:2", "a:3"]);
+    TestCase::new_for_json_tests()
+        .add_file("a", lines!["a", "a", "a", "a"])
+        .add_rule(lines![
+            "files a",
+            "match /a/ within last 2 lines",
+            "matches == 2"
+        ])
+        .run()
+        .assert_matches(vec!["a:3", "a:4"]);
+}
+
+#[test]
+fn without_match() {
+    TestCase::new_for_json_tests()
+        .add_rule(lines!["files a", "matches > 2"])
+        .silence_stderr()
+        .run()
+        .assert_failure();
+}
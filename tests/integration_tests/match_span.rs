@@ -0,0 +1,101 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use testutils::{TestCase, lines};
+
+fn get_span(test_case: &mut TestCase) -> (serde_json::Value, serde_json::Value, serde_json::Value) {
+    let json = test_case.run().stdout_json();
+    let m = &json[0];
+    (
+        m["column"].clone(),
+        m["end_column"].clone(),
+        m["text"].clone(),
+    )
+}
+
+#[test]
+fn basic() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["import foo"])
+        .add_rule(lines!["files a.py", "match /fo+/"]);
+    assert_eq!(
+        get_span(&mut test_case),
+        (8.into(), 11.into(), "import foo".into())
+    );
+}
+
+#[test]
+fn first_matching_pattern() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["foo bar"])
+        .add_rule(lines!["files a.py", "match /baz/ /bar/ /foo/"]);
+    assert_eq!(
+        get_span(&mut test_case),
+        (5.into(), 8.into(), "foo bar".into())
+    );
+}
+
+#[test]
+fn byte_columns() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["# ☃ foo"])
+        .add_rule(lines!["files a.py", "match /foo/"]);
+    assert_eq!(
+        get_span(&mut test_case),
+        (7.into(), 10.into(), "# ☃ foo".into())
+    );
+}
+
+#[test]
+fn follow_up() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["try:", "    pass", "    except:"])
+        .add_rule(lines!["files a.py", "match /try:/ then /except:/"]);
+    assert_eq!(
+        get_span(&mut test_case),
+        (5.into(), 12.into(), "    except:".into())
+    );
+}
+
+#[test]
+fn deferred() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["foo", "x foo"])
+        .add_rule(lines!["files a.py", "match /foo/ within last 1 line"]);
+    assert_eq!(
+        get_span(&mut test_case),
+        (3.into(), 6.into(), "x foo".into())
+    );
+}
+
+#[test]
+fn truncated_text() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", &format!("{}foo\n", "☃".repeat(200)))
+        .add_rule(lines!["files a.py", "match /foo/"]);
+    let (column, end_column, text) = get_span(&mut test_case);
+    assert_eq!((column, end_column), (601.into(), 604.into()));
+    assert_eq!(text.as_str().unwrap(), "☃".repeat(170));
+}
+
+#[test]
+fn not_present_for_files() {
+    let mut test_case = TestCase::new_for_json_tests();
+    test_case
+        .add_file("a.py", lines!["foo"])
+        .add_rule(lines!["files a.py"]);
+    assert_eq!(
+        get_span(&mut test_case),
+        (
+            serde_json::Value::Null,
+            serde_json::Value::Null,
+            serde_json::Value::Null
+        )
+    );
+}
@@ -1,15 +1,33 @@
 // SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
 // SPDX-License-Identifier: GPL-3.0-or-later
 
+mod baseline;
 mod conditionless_rule;
 mod content_condition_combinations;
 mod content_conditions;
+mod context_lines;
 mod error_exitcode;
+mod explain;
+mod fix;
+mod follow_up;
+mod format_checkstyle;
+mod format_csv;
+mod format_github;
+mod format_gitlab;
+mod format_jsonl;
+mod format_junit;
+mod format_matrix;
+mod format_report;
+mod format_sarif;
 mod glob_patterns;
 mod glob_scope;
+mod ignore_file;
 mod ignore_marker;
 mod includes;
+mod line_range;
 mod lines_condition;
+mod match_count;
+mod match_span;
 mod multiple_rules;
 mod parsing_errors;
 mod path_condition_combinations;
@@ -19,4 +37,5 @@ mod regexp_patterns;
 mod reporting_target;
 mod size_condition;
 mod stdout;
+mod summary;
 mod tags;
@@ -116,3 +116,50 @@ fn match_with_exclusion_pattern_only() {
 fn match_with_pattern_after_exclusion() {
     check_parsing_error(lines!["[rule]", "files *", "match /./ !/./ /./"]);
 }
+
+// error recovery
+
+#[test]
+fn multiple_errors_reported() {
+    TestCase::new_for_json_tests()
+        .add_raw_rule(lines![
+            "[first]",
+            "files ***",
+            "[second]",
+            "xxx",
+            "[third]",
+            "files *"
+        ])
+        .silence_stderr()
+        .run()
+        .assert_failure()
+        .assert_stderr_contains("--> omnilinter.conf:2")
+        .assert_stderr_contains("--> omnilinter.conf:4")
+        .assert_stderr_contains("2 error(s) found in config");
+}
+
+#[test]
+fn errors_in_included_files_reported() {
+    TestCase::new_for_json_tests()
+        .add_raw_file(
+            "omnilinter.conf",
+            lines!["include second.conf", "[first]", "xxx"],
+        )
+        .add_raw_file("second.conf", lines!["[second]", "xxx"])
+        .silence_stderr()
+        .run()
+        .assert_failure()
+        .assert_stderr_contains("--> omnilinter.conf:3")
+        .assert_stderr_contains("--> second.conf:2");
+}
+
+#[test]
+fn keep_going() {
+    TestCase::new_for_json_tests()
+        .add_arg("--keep-going")
+        .add_file("a", "")
+        .add_raw_rule(lines!["[first]", "files ***", "[second]", "files a"])
+        .silence_stderr()
+        .run()
+        .assert_matches(vec!["a"]);
+}
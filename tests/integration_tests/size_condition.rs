@@ -111,6 +111,35 @@ fn not_equal_not_matching() {
         .assert_matches(vec![]);
 }
 
+#[test]
+fn range_matching() {
+    TestCase::new_for_json_tests()
+        .add_file("a", "0123456789")
+        .add_file("b", "01234567890")
+        .add_file("c", "012345678")
+        .add_rule(lines!["files *", "size 10..11"])
+        .run()
+        .assert_matches(vec!["a", "b"]);
+}
+
+#[test]
+fn range_not_matching() {
+    TestCase::new_for_json_tests()
+        .add_file("a", "0123456789")
+        .add_rule(lines!["files a", "size 11..20"])
+        .run()
+        .assert_matches(vec![]);
+}
+
+#[test]
+fn units() {
+    TestCase::new_for_json_tests()
+        .add_file("a", &"x".repeat(1536))
+        .add_rule(lines!["files a", "size 1.5K..1.5K"])
+        .run()
+        .assert_matches(vec!["a"]);
+}
+
 #[test]
 #[ignore]
 fn binary_file() {
@@ -19,13 +19,13 @@ fn format_by_root() {
         .add_file("file", lines!["line"])
         .add_named_rule("rootrule", "")
         .add_named_rule("filerule", lines!["files *"])
-        .add_named_rule("linerule", lines!["files *", "match /./"])
+        .add_named_rule("linerule", lines!["files *", "match /ne/"])
         .run()
         .assert_stdout(lines![
             "root",
             "  rootrule",
             "  file: filerule",
-            "  file:1: linerule"
+            "  file:1:3: linerule"
         ]);
 }
 
@@ -36,12 +36,12 @@ fn format_full_paths() {
         .add_file("file", lines!["line"])
         .add_named_rule("rootrule", "")
         .add_named_rule("filerule", lines!["files *"])
-        .add_named_rule("linerule", lines!["files *", "match /./"])
+        .add_named_rule("linerule", lines!["files *", "match /ne/"])
         .run()
         .assert_stdout(paths!(lines![
             "root: rootrule",
             "root/file: filerule",
-            "root/file:1: linerule"
+            "root/file:1:3: linerule"
         ]));
 }
 
@@ -52,7 +52,7 @@ fn format_by_rule() {
         .add_file("file", lines!["line"])
         .add_named_rule("rootrule", "")
         .add_named_rule("filerule", lines!["files *"])
-        .add_named_rule("linerule", lines!["files *", "match /./"])
+        .add_named_rule("linerule", lines!["files *", "match /ne/"])
         .run()
         .assert_stdout(paths!(lines![
             "rootrule",
@@ -60,7 +60,7 @@ fn format_by_rule() {
             "filerule",
             "  root/file",
             "linerule",
-            "  root/file:1"
+            "  root/file:1:3"
         ]));
 }
 
@@ -71,13 +71,13 @@ fn format_by_path() {
         .add_file("file", lines!["line"])
         .add_named_rule("rootrule", "")
         .add_named_rule("filerule", lines!["files *"])
-        .add_named_rule("linerule", lines!["files *", "match /./"])
+        .add_named_rule("linerule", lines!["files *", "match /ne/"])
         .run()
         .assert_stdout(paths!(lines![
             "root",
             "  rootrule",
             "root/file",
             "  filerule",
-            "  line 1: linerule"
+            "  line 1, column 3: linerule"
         ]));
 }
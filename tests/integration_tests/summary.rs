@@ -0,0 +1,75 @@
+// SPDX-FileCopyrightText: Copyright 2024 Dmitry Marakasov <amdmi3@amdmi3.ru>
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+use testutils::{TestCase, lines};
+
+#[test]
+fn text() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--summary")
+        .add_file("a.py", lines!["foo", "foo"])
+        .add_file("b.py", lines!["bar"])
+        .add_named_rule("root", "")
+        .add_named_rule("line", lines!["files a.py", "match /foo/"])
+        .run()
+        .assert_stdout_contains(lines![
+            "root",
+            "  root",
+            "  a.py:1:1: line",
+            "  a.py:2:1: line",
+            "",
+            "Matches per rule:",
+            "  root: 1",
+            "  line: 2",
+            "Matches per root:",
+            "  root: 3",
+            "Total: 3 match(es) in 1 of 1 root(s)"
+        ])
+        .assert_stdout_contains("Walked 2 file(s), read 1 file(s) (8 byte(s)) in ");
+}
+
+#[test]
+fn text_no_matches() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--summary")
+        .add_file("a.py", lines!["foo"])
+        .add_rule(lines!["files b.py"])
+        .run()
+        .assert_stdout_contains(lines!["Total: 0 match(es) in 0 of 1 root(s)"])
+        .assert_stdout_contains("Walked 1 file(s), read 0 file(s) (0 byte(s)) in ");
+}
+
+#[test]
+fn json() {
+    let mut test_case = TestCase::new_for_stdout_tests();
+    test_case
+        .add_arg("--format=json")
+        .add_arg("--summary")
+        .add_file("a.py", lines!["foo", "foo"])
+        .add_named_rule("line", lines!["files a.py", "match /foo/"]);
+    let result = test_case.run();
+    let json = result.stdout_json();
+
+    assert_eq!(json["matches"].as_array().unwrap().len(), 2);
+    let stats = &json["stats"];
+    assert_eq!(stats["matches"], 2);
+    assert_eq!(stats["rules"][0]["rule"], "line");
+    assert_eq!(stats["rules"][0]["matches"], 2);
+    assert_eq!(stats["roots"][0]["matches"], 2);
+    assert_eq!(stats["roots_scanned"], 1);
+    assert_eq!(stats["files_walked"], 1);
+    assert_eq!(stats["files_read"], 1);
+    assert_eq!(stats["bytes_read"], 8);
+    assert!(stats["elapsed_seconds"].is_f64());
+}
+
+#[test]
+fn machine_readable_formats_are_not_broken() {
+    TestCase::new_for_stdout_tests()
+        .add_arg("--format=csv")
+        .add_arg("--summary")
+        .add_named_rule("root", "")
+        .run()
+        .assert_stdout(lines!["root,path,line,rule,tags", "root,,,root,"])
+        .assert_stderr_contains("Total: 1 match(es) in 1 of 1 root(s)");
+}
@@ -41,6 +41,7 @@ pub struct Match {
 pub struct TestCase {
     temp_dir: TempDir,
     args: Vec<String>,
+    envs: Vec<(String, String)>,
     silence_stderr: bool,
 }
 
@@ -56,6 +57,7 @@ impl TestCase {
                 .into_iter()
                 .map(|a| a.to_string())
                 .collect(),
+            envs: vec![],
             silence_stderr: false,
         }
     }
@@ -71,6 +73,7 @@ impl TestCase {
                 .into_iter()
                 .map(|a| a.to_string())
                 .collect(),
+            envs: vec![],
             silence_stderr: false,
         }
     }
@@ -105,12 +108,28 @@ impl TestCase {
         self
     }
 
+    pub fn read_file(&self, path: &str) -> String {
+        fs::read_to_string(self.temp_dir.path().join("root").join(path)).unwrap()
+    }
+
     pub fn add_arg(&mut self, arg: &str) -> &mut Self {
         self.args.push(arg.to_string());
 
         self
     }
 
+    pub fn set_env(&mut self, key: &str, value: &str) -> &mut Self {
+        self.envs.push((key.to_string(), value.to_string()));
+
+        self
+    }
+
+    pub fn remove_arg(&mut self, arg: &str) -> &mut Self {
+        self.args.retain(|a| a != arg);
+
+        self
+    }
+
     pub fn silence_stderr(&mut self) -> &mut Self {
         self.silence_stderr = true;
 
@@ -168,6 +187,10 @@ impl TestCase {
             cmd.arg(arg);
         }
 
+        for (key, value) in &self.envs {
+            cmd.env(key, value);
+        }
+
         let output = cmd.output().unwrap();
 
         if !self.silence_stderr {
@@ -227,11 +250,20 @@ impl TestRunResult {
         self
     }
 
+    pub fn assert_stderr_not_contains(&self, sample: &str) -> &Self {
+        assert!(!from_utf8(&self.output.stderr).unwrap().contains(sample));
+        self
+    }
+
     pub fn assert_stdout_contains(&self, sample: &str) -> &Self {
         assert!(from_utf8(&self.output.stdout).unwrap().contains(sample));
         self
     }
 
+    pub fn stdout_json(&self) -> serde_json::Value {
+        serde_json::from_slice(&self.output.stdout).unwrap()
+    }
+
     pub fn assert_stdout(&self, expected: &str) -> &Self {
         pretty_assertions::assert_eq!(from_utf8(&self.output.stdout).unwrap(), expected);
         self